@@ -0,0 +1,179 @@
+use crate::behaviour_tree::BehaviourTree;
+use crate::common::Statistics;
+use crate::comms::{OutgoingMessage, RobotMessage};
+use crate::config::{AllocationStrategy, SimulationConfig};
+use crate::energy::{Disabled, Recharging};
+use crate::knowledge::KnownResources;
+use crate::pathfinding::PathFollower;
+use crate::robot::{AllocationSet, Cargo, Collector, Returning, Robot, Target};
+use bevy::prelude::*;
+
+pub struct AuctionPlugin;
+
+impl Plugin for AuctionPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<TaskAwarded>();
+        app.add_systems(FixedUpdate, run_auctions.in_set(AllocationSet));
+    }
+}
+
+// Offre d'un collecteur pour une ressource : temps de trajet estimé, en secondes
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Bid {
+    pub bidder: Entity,
+    pub resource: Entity,
+    pub position: Vec2,
+    pub cost: f32,
+}
+
+// Ressource attribuée à un collecteur à l'issue d'une enchère
+#[derive(Event, Clone, Copy, Debug, PartialEq)]
+pub struct TaskAwarded {
+    pub resource: Entity,
+    pub position: Vec2,
+    pub winner: Entity,
+    pub cost: f32,
+    pub previous: Option<Entity>, // Collecteur à qui la ressource a été retirée
+}
+
+pub fn bid_cost(from: Vec2, to: Vec2, speed: f32) -> f32 {
+    from.distance(to) / speed
+}
+
+// Attribue les ressources par offres croissantes, chaque collecteur en remportant au plus une.
+// Une ressource déjà attribuée (`holders`, avec le coût restant de son collecteur) ne change
+// de main que si l'offre est plus basse d'au moins `margin`
+pub fn award(mut bids: Vec<Bid>, holders: &[Bid], margin: f32) -> Vec<TaskAwarded> {
+    bids.sort_by(|a, b| a.cost.total_cmp(&b.cost).then(a.bidder.cmp(&b.bidder)));
+
+    let mut awards: Vec<TaskAwarded> = vec![];
+    for bid in bids {
+        if awards
+            .iter()
+            .any(|award| award.winner == bid.bidder || award.resource == bid.resource)
+        {
+            continue;
+        }
+
+        let holder = holders
+            .iter()
+            .find(|holder| holder.resource == bid.resource);
+        if holder.is_some_and(|holder| bid.cost + margin >= holder.cost) {
+            continue;
+        }
+
+        awards.push(TaskAwarded {
+            resource: bid.resource,
+            position: bid.position,
+            winner: bid.bidder,
+            cost: bid.cost,
+            previous: holder.map(|holder| holder.bidder),
+        });
+    }
+
+    awards
+}
+
+// La base tient une enchère à chaque pas de temps : les collecteurs libres enchérissent sur
+// les ressources qu'ils connaissent, libres ou attribuées à un collecteur qui pourrait être battu.
+// Un collecteur hors service ou en recharge a déjà libéré ses réservations, qui sont remises en jeu.
+// Un collecteur guidé par un arbre de comportement choisit lui-même ses ressources
+#[allow(clippy::type_complexity)]
+fn run_auctions(
+    mut commands: Commands,
+    collectors: Query<
+        (Entity, &Transform, &Robot, &Cargo, Option<&Target>),
+        (
+            With<Collector>,
+            Without<Returning>,
+            Without<Recharging>,
+            Without<Disabled>,
+            Without<BehaviourTree>,
+        ),
+    >,
+    mut known: KnownResources,
+    mut outgoing: EventWriter<OutgoingMessage>,
+    mut awarded: EventWriter<TaskAwarded>,
+    mut statistics: ResMut<Statistics>,
+    config: Res<SimulationConfig>,
+) {
+    if config.allocation.strategy != AllocationStrategy::Auction {
+        return;
+    }
+
+    let holders: Vec<Bid> = collectors
+        .iter()
+        .filter_map(|(entity, transform, robot, _, target)| {
+            target.map(|target| Bid {
+                bidder: entity,
+                resource: target.resource,
+                position: target.position,
+                cost: bid_cost(
+                    transform.translation.truncate(),
+                    target.position,
+                    robot.speed,
+                ),
+            })
+        })
+        .collect();
+
+    let mut bids = vec![];
+    for (entity, transform, robot, cargo, target) in &collectors {
+        if target.is_some() || cargo.is_full() {
+            continue;
+        }
+        let position = transform.translation.truncate();
+
+        for discovery in &known.of(entity).0 {
+            if discovery.unreachable_for.contains(&entity) {
+                continue;
+            }
+            let open = discovery.claimed_by.is_none_or(|claimant| {
+                holders.iter().any(|holder| {
+                    holder.bidder == claimant && holder.resource == discovery.resource
+                })
+            });
+            if open {
+                bids.push(Bid {
+                    bidder: entity,
+                    resource: discovery.resource,
+                    position: discovery.position,
+                    cost: bid_cost(position, discovery.position, robot.speed),
+                });
+            }
+        }
+    }
+
+    for award in award(bids, &holders, config.allocation.reassign_margin) {
+        if let Some(previous) = award.previous {
+            commands.entity(previous).remove::<(Target, PathFollower)>();
+            statistics.tasks_reassigned += 1;
+        }
+        statistics.tasks_awarded += 1;
+
+        for robot in [Some(award.winner), award.previous].into_iter().flatten() {
+            if let Some(discovery) = known.of(robot).get_mut(award.resource) {
+                discovery.claimed_by = Some(award.winner);
+            }
+        }
+
+        let Ok((_, transform, ..)) = collectors.get(award.winner) else {
+            continue;
+        };
+        outgoing.send(OutgoingMessage {
+            sender: award.winner,
+            position: transform.translation.truncate(),
+            message: RobotMessage::Claim {
+                resource: award.resource,
+            },
+        });
+        commands.entity(award.winner).insert((
+            Target {
+                resource: award.resource,
+                position: award.position,
+            },
+            PathFollower::new(award.position),
+        ));
+        awarded.send(award);
+    }
+}
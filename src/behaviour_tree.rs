@@ -0,0 +1,494 @@
+use crate::common::{Base, DiscoveredResources};
+use crate::comms::{OutgoingMessage, RobotMessage};
+use crate::config::{AllocationStrategy, SimulationConfig};
+use crate::energy::{Battery, Disabled, Recharging};
+use crate::frontier::{explored_by, frontier_clusters, FrontierTarget, UnreachableFrontiers};
+use crate::knowledge::KnownResources;
+use crate::map::Map;
+use crate::pathfinding::{PathFollower, PathfindingSet};
+use crate::robot::{AllocationSet, Cargo, Returning, Role, Sensor, Target};
+use bevy::{ecs::system::SystemParam, prelude::*, utils::HashMap};
+use serde::Deserialize;
+use std::time::Duration;
+
+pub struct BehaviourTreePlugin;
+
+impl Plugin for BehaviourTreePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            FixedUpdate,
+            (attach_trees, tick_trees)
+                .chain()
+                .after(AllocationSet)
+                .before(PathfindingSet),
+        );
+    }
+}
+
+// Nœud d'un arbre de comportement, tel qu'écrit dans la configuration
+#[derive(Deserialize, Clone, Debug, PartialEq)]
+pub enum Node {
+    Sequence(Vec<Node>), // Réussit si tous ses enfants réussissent, dans l'ordre
+    Selector(Vec<Node>), // Réussit dès qu'un enfant réussit, dans l'ordre
+    Parallel(usize, Vec<Node>), // Exécute tous ses enfants, réussit si au moins n réussissent
+    Invert(Box<Node>),
+    ForceSuccess(Box<Node>),
+    Cooldown(f32, Box<Node>), // Échoue pendant ce nombre de secondes après un résultat de l'enfant
+    Condition(Condition),
+    Action(Action),
+}
+
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq)]
+pub enum Condition {
+    CargoFull,
+    CargoEmpty,
+    HasTarget,
+    KnowsResource,  // Une ressource connue est libre et accessible
+    SensesResource, // Le capteur détecte une ressource
+    BatteryBelow(f32),
+    AtBase,
+}
+
+// Chaque action prend la main sur le robot : elle abandonne les buts des autres actions
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Action {
+    Explore,      // Rejoindre la frontière libre la plus proche
+    Collect,      // Aller chercher la ressource réservée, ou réserver la plus proche
+    ReturnToBase, // Réussit une fois à la base
+    Wander,       // Marche aléatoire
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Status {
+    Success,
+    Failure,
+    Running,
+}
+
+// Feuilles de l'arbre : conditions et actions évaluées pour un robot
+pub trait Leaves {
+    fn condition(&mut self, robot: Entity, condition: Condition) -> bool;
+    fn action(&mut self, robot: Entity, action: Action) -> Status;
+}
+
+impl Node {
+    // Nombre de nœuds du sous-arbre, pour numéroter les nœuds en préordre
+    fn size(&self) -> usize {
+        1 + match self {
+            Node::Sequence(children) | Node::Selector(children) | Node::Parallel(_, children) => {
+                children.iter().map(Node::size).sum()
+            }
+            Node::Invert(child) | Node::ForceSuccess(child) | Node::Cooldown(_, child) => {
+                child.size()
+            }
+            Node::Condition(_) | Node::Action(_) => 0,
+        }
+    }
+
+    // Vérifie les paramètres des nœuds, retourne la raison d'un refus
+    pub fn check(&self) -> Result<(), &'static str> {
+        match self {
+            Node::Sequence(children) | Node::Selector(children) if children.is_empty() => {
+                Err("a sequence or selector needs at least one child")
+            }
+            Node::Parallel(threshold, children)
+                if *threshold == 0 || *threshold > children.len() =>
+            {
+                Err("a parallel threshold must be between 1 and the number of children")
+            }
+            Node::Cooldown(seconds, _) if !(seconds.is_finite() && *seconds >= 0.0) => {
+                Err("a cooldown must not be negative")
+            }
+            Node::Condition(Condition::BatteryBelow(level)) if !(0.0..=1.0).contains(level) => {
+                Err("a battery level must be between 0 and 1")
+            }
+            Node::Sequence(children) | Node::Selector(children) | Node::Parallel(_, children) => {
+                children.iter().try_for_each(Node::check)
+            }
+            Node::Invert(child) | Node::ForceSuccess(child) | Node::Cooldown(_, child) => {
+                child.check()
+            }
+            Node::Condition(_) | Node::Action(_) => Ok(()),
+        }
+    }
+}
+
+// Arbre de comportement d'un robot, parcouru depuis la racine à chaque pas de temps
+#[derive(Component, Clone, Debug)]
+pub struct BehaviourTree {
+    pub root: Node,
+    pub status: Option<Status>,         // Résultat du dernier parcours
+    ready_at: HashMap<usize, Duration>, // Fin du temps de recharge de chaque `Cooldown`, par nœud
+}
+
+impl BehaviourTree {
+    pub fn new(root: Node) -> Self {
+        Self {
+            root,
+            status: None,
+            ready_at: HashMap::default(),
+        }
+    }
+
+    // `now` est le temps simulé écoulé
+    pub fn tick(&mut self, robot: Entity, leaves: &mut impl Leaves, now: Duration) -> Status {
+        let status = tick_node(&self.root, 0, &mut self.ready_at, robot, leaves, now);
+        self.status = Some(status);
+        status
+    }
+}
+
+fn tick_node(
+    node: &Node,
+    id: usize,
+    ready_at: &mut HashMap<usize, Duration>,
+    robot: Entity,
+    leaves: &mut impl Leaves,
+    now: Duration,
+) -> Status {
+    match node {
+        Node::Sequence(children) | Node::Selector(children) => {
+            // Une séquence s'arrête au premier échec, un sélecteur à la première réussite
+            let keep_going = match node {
+                Node::Sequence(_) => Status::Success,
+                _ => Status::Failure,
+            };
+            let mut child_id = id + 1;
+            for child in children {
+                let status = tick_node(child, child_id, ready_at, robot, leaves, now);
+                if status != keep_going {
+                    return status;
+                }
+                child_id += child.size();
+            }
+            keep_going
+        }
+        Node::Parallel(threshold, children) => {
+            let (mut successes, mut failures) = (0, 0);
+            let mut child_id = id + 1;
+            for child in children {
+                match tick_node(child, child_id, ready_at, robot, leaves, now) {
+                    Status::Success => successes += 1,
+                    Status::Failure => failures += 1,
+                    Status::Running => {}
+                }
+                child_id += child.size();
+            }
+
+            if successes >= *threshold {
+                Status::Success
+            } else if failures > children.len() - threshold {
+                Status::Failure
+            } else {
+                Status::Running
+            }
+        }
+        Node::Invert(child) => match tick_node(child, id + 1, ready_at, robot, leaves, now) {
+            Status::Success => Status::Failure,
+            Status::Failure => Status::Success,
+            Status::Running => Status::Running,
+        },
+        Node::ForceSuccess(child) => match tick_node(child, id + 1, ready_at, robot, leaves, now) {
+            Status::Running => Status::Running,
+            _ => Status::Success,
+        },
+        Node::Cooldown(seconds, child) => {
+            if ready_at.get(&id).is_some_and(|&ready| now < ready) {
+                return Status::Failure;
+            }
+            let status = tick_node(child, id + 1, ready_at, robot, leaves, now);
+            if status != Status::Running {
+                let cooldown = Duration::try_from_secs_f32(*seconds).unwrap_or(Duration::MAX);
+                ready_at.insert(id, now.saturating_add(cooldown));
+            }
+            status
+        }
+        Node::Condition(condition) => {
+            if leaves.condition(robot, *condition) {
+                Status::Success
+            } else {
+                Status::Failure
+            }
+        }
+        Node::Action(action) => leaves.action(robot, *action),
+    }
+}
+
+// Feuilles reliées aux systèmes des robots : les actions posent les composants
+// (`Target`, `FrontierTarget`, `Returning`, `PathFollower`) que ces systèmes exécutent
+#[allow(clippy::type_complexity)]
+#[derive(SystemParam)]
+pub struct RobotLeaves<'w, 's> {
+    commands: Commands<'w, 's>,
+    robots: Query<
+        'w,
+        's,
+        (
+            &'static Transform,
+            &'static Children,
+            Option<&'static Cargo>,
+            Option<&'static Battery>,
+            Option<&'static Target>,
+            Option<&'static FrontierTarget>,
+            Option<&'static PathFollower>,
+            Option<&'static UnreachableFrontiers>,
+            Has<Returning>,
+        ),
+    >,
+    sensors: Query<'w, 's, &'static Sensor>,
+    base: Query<'w, 's, (&'static Transform, &'static Base)>,
+    map: Query<'w, 's, &'static Map>,
+    known: KnownResources<'w, 's>,
+    outgoing: EventWriter<'w, OutgoingMessage>,
+    config: Res<'w, SimulationConfig>,
+    frontier_goals: Local<'s, Vec<Vec2>>, // Frontières choisies pendant ce pas de temps
+}
+
+impl RobotLeaves<'_, '_> {
+    // À appeler au début de chaque pas de temps
+    pub fn clear(&mut self) {
+        self.frontier_goals.clear();
+    }
+
+    // Ressources connues d'un robot
+    pub fn discoveries(&mut self, robot: Entity) -> &DiscoveredResources {
+        self.known.of(robot)
+    }
+
+    fn base(&self) -> Option<(Vec2, f32)> {
+        let (transform, base) = self.base.get_single().ok()?;
+        Some((transform.translation.truncate(), base.radius))
+    }
+
+    // Abandonne les buts posés par les autres actions
+    fn drop_intents(&mut self, robot: Entity, keep: Action) {
+        let Ok((.., target, frontier, follower, _, returning)) = self.robots.get(robot) else {
+            return;
+        };
+        let drop_target = target.is_some() && keep != Action::Collect;
+        let drop_frontier = frontier.is_some() && keep != Action::Explore;
+        let drop_returning = returning && keep != Action::ReturnToBase;
+        let drop_path = follower.is_some()
+            && (drop_target || drop_frontier || drop_returning || keep == Action::Wander);
+
+        if drop_target {
+            self.known.release(robot);
+        }
+        let mut entity = self.commands.entity(robot);
+        if drop_target {
+            entity.remove::<Target>();
+        }
+        if drop_frontier {
+            entity.remove::<FrontierTarget>();
+        }
+        if drop_returning {
+            entity.remove::<Returning>();
+        }
+        if drop_path {
+            entity.remove::<PathFollower>();
+        }
+    }
+
+    fn explore(&mut self, robot: Entity) -> Status {
+        self.drop_intents(robot, Action::Explore);
+        let Ok((transform, .., frontier, _, unreachable, _)) = self.robots.get(robot) else {
+            return Status::Failure;
+        };
+        if frontier.is_some() {
+            return Status::Running;
+        }
+        let Ok(map) = self.map.get_single() else {
+            return Status::Failure;
+        };
+        let position = transform.translation.truncate();
+        let exploration = &self.config.exploration;
+
+        let mut goals: Vec<Vec2> = self
+            .robots
+            .iter()
+            .filter_map(|(.., frontier, _, _, _)| frontier.map(|frontier| frontier.goal))
+            .collect();
+        goals.extend(self.frontier_goals.iter());
+
+        let clusters = frontier_clusters(
+            map,
+            &explored_by(map, self.known.knowledge(robot)),
+            exploration.min_frontier_size,
+        );
+        let closest = clusters
+            .iter()
+            .map(|cluster| (cluster.target, map.cell_center(cluster.target)))
+            .filter(|(cell, _)| unreachable.is_none_or(|unreachable| !unreachable.0.contains(cell)))
+            .filter(|(_, goal)| {
+                goals
+                    .iter()
+                    .all(|other| other.distance(*goal) >= exploration.spread)
+            })
+            .min_by(|(_, a), (_, b)| {
+                a.distance_squared(position)
+                    .total_cmp(&b.distance_squared(position))
+            });
+
+        let Some((cell, goal)) = closest else {
+            return Status::Failure;
+        };
+        self.frontier_goals.push(goal);
+        let mut entity = self.commands.entity(robot);
+        entity.insert((FrontierTarget { goal, cell }, PathFollower::new(goal)));
+        if unreachable.is_none() {
+            entity.insert(UnreachableFrontiers::default());
+        }
+        Status::Running
+    }
+
+    fn collect(&mut self, robot: Entity) -> Status {
+        let Ok((transform, _, cargo, _, target, ..)) = self.robots.get(robot) else {
+            return Status::Failure;
+        };
+        if cargo.is_none_or(|cargo| cargo.is_full()) {
+            return Status::Failure;
+        }
+        let position = transform.translation.truncate();
+        let has_target = target.is_some();
+
+        self.drop_intents(robot, Action::Collect);
+        if has_target {
+            return Status::Running;
+        }
+        // Aux enchères, la ressource est attribuée dans AllocationSet
+        if self.config.allocation.strategy != AllocationStrategy::Nearest {
+            return Status::Failure;
+        }
+
+        let closest = self
+            .known
+            .of(robot)
+            .0
+            .iter_mut()
+            .filter(|discovery| {
+                discovery.claimed_by.is_none() && !discovery.unreachable_for.contains(&robot)
+            })
+            .min_by(|a, b| {
+                a.position
+                    .distance_squared(position)
+                    .total_cmp(&b.position.distance_squared(position))
+            });
+        let Some(discovery) = closest else {
+            return Status::Failure;
+        };
+
+        discovery.claimed_by = Some(robot);
+        let (resource, goal) = (discovery.resource, discovery.position);
+        self.outgoing.send(OutgoingMessage {
+            sender: robot,
+            position,
+            message: RobotMessage::Claim { resource },
+        });
+        self.commands.entity(robot).insert((
+            Target {
+                resource,
+                position: goal,
+            },
+            PathFollower::new(goal),
+        ));
+        Status::Running
+    }
+
+    fn return_to_base(&mut self, robot: Entity) -> Status {
+        let Some((base_position, base_radius)) = self.base() else {
+            return Status::Failure;
+        };
+        let Ok((transform, .., follower, _, returning)) = self.robots.get(robot) else {
+            return Status::Failure;
+        };
+        let at_base = transform.translation.truncate().distance(base_position) < base_radius;
+        let heading_home = follower.is_some_and(|follower| follower.goal == base_position);
+
+        self.drop_intents(robot, Action::ReturnToBase);
+        let mut entity = self.commands.entity(robot);
+        if at_base {
+            if returning {
+                entity.remove::<(Returning, PathFollower)>();
+            }
+            return Status::Success;
+        }
+
+        if !returning {
+            entity.insert(Returning);
+        }
+        if !heading_home {
+            entity.insert(PathFollower::new(base_position));
+        }
+        Status::Running
+    }
+}
+
+impl Leaves for RobotLeaves<'_, '_> {
+    fn condition(&mut self, robot: Entity, condition: Condition) -> bool {
+        let Ok((transform, children, cargo, battery, target, ..)) = self.robots.get(robot) else {
+            return false;
+        };
+
+        match condition {
+            Condition::CargoFull => cargo.is_some_and(|cargo| cargo.is_full()),
+            Condition::CargoEmpty => cargo.is_none_or(|cargo| cargo.items.is_empty()),
+            Condition::HasTarget => target.is_some(),
+            Condition::KnowsResource => self.known.of(robot).0.iter().any(|discovery| {
+                discovery.claimed_by.is_none() && !discovery.unreachable_for.contains(&robot)
+            }),
+            Condition::SensesResource => children
+                .iter()
+                .filter_map(|&child| self.sensors.get(child).ok())
+                .any(|sensor| sensor.nearest.is_some()),
+            Condition::BatteryBelow(level) => {
+                battery.is_some_and(|battery| battery.level() < level)
+            }
+            Condition::AtBase => self.base().is_some_and(|(base_position, base_radius)| {
+                transform.translation.truncate().distance(base_position) < base_radius
+            }),
+        }
+    }
+
+    fn action(&mut self, robot: Entity, action: Action) -> Status {
+        match action {
+            Action::Explore => self.explore(robot),
+            Action::Collect => self.collect(robot),
+            Action::ReturnToBase => self.return_to_base(robot),
+            Action::Wander => {
+                self.drop_intents(robot, Action::Wander);
+                Status::Running
+            }
+        }
+    }
+}
+
+// Les robots dont le rôle a un arbre dans la configuration le reçoivent à leur apparition
+fn attach_trees(
+    mut commands: Commands,
+    robots: Query<(Entity, &Role), Added<Role>>,
+    config: Res<SimulationConfig>,
+) {
+    for (entity, role) in &robots {
+        if let Some(root) = config.behaviours.get(&role.0) {
+            commands
+                .entity(entity)
+                .insert(BehaviourTree::new(root.clone()));
+        }
+    }
+}
+
+// La gestion de l'énergie reste prioritaire : un robot en recharge ou hors service
+// ne parcourt pas son arbre
+#[allow(clippy::type_complexity)]
+fn tick_trees(
+    mut trees: Query<(Entity, &mut BehaviourTree), (Without<Recharging>, Without<Disabled>)>,
+    mut leaves: RobotLeaves,
+    time: Res<Time>,
+) {
+    leaves.clear();
+    let now = time.elapsed();
+
+    for (entity, mut tree) in trees.iter_mut() {
+        tree.tick(entity, &mut leaves, now);
+    }
+}
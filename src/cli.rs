@@ -0,0 +1,124 @@
+use crate::config::{ConfigError, SimulationConfig};
+use std::{fmt, path::PathBuf};
+
+pub const USAGE: &str = "\
+Usage: essaim_robots [OPTIONS]
+
+Options:
+  --config <PATH>       Fichier de configuration RON (par défaut: config.ron s'il existe)
+  --seed <N>            Graine de la simulation
+  --explorers <N>       Nombre d'explorateurs
+  --collectors <N>      Nombre de collecteurs
+  --map-width <N>       Largeur de la carte en tuiles
+  --map-height <N>      Hauteur de la carte en tuiles
+  --duration <SECONDS>  Durée simulée avant l'arrêt
+  --headless            Exécution sans fenêtre ni GPU
+  -h, --help            Affiche cette aide";
+
+const DEFAULT_CONFIG_PATH: &str = "config.ron";
+
+// Options de la ligne de commande, prioritaires sur le fichier de configuration
+#[derive(Default, Debug)]
+pub struct Cli {
+    pub config: Option<PathBuf>,
+    pub seed: Option<u32>,
+    pub explorers: Option<usize>,
+    pub collectors: Option<usize>,
+    pub map_width: Option<u32>,
+    pub map_height: Option<u32>,
+    pub duration: Option<f32>,
+    pub headless: bool,
+    pub help: bool,
+}
+
+#[derive(Debug)]
+pub enum CliError {
+    UnknownArgument(String),
+    MissingValue(String),
+    InvalidValue { flag: String, value: String },
+    Config(ConfigError),
+}
+
+impl fmt::Display for CliError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CliError::UnknownArgument(arg) => write!(f, "unknown argument `{arg}`"),
+            CliError::MissingValue(flag) => write!(f, "missing value for `{flag}`"),
+            CliError::InvalidValue { flag, value } => {
+                write!(f, "invalid value `{value}` for `{flag}`")
+            }
+            CliError::Config(error) => error.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for CliError {}
+
+impl Cli {
+    pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Self, CliError> {
+        let mut cli = Cli::default();
+        let mut args = args.into_iter();
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--headless" => cli.headless = true,
+                "-h" | "--help" => cli.help = true,
+                "--config" => cli.config = Some(PathBuf::from(value(&arg, args.next())?)),
+                "--seed" => cli.seed = Some(parse(&arg, args.next())?),
+                "--explorers" => cli.explorers = Some(parse(&arg, args.next())?),
+                "--collectors" => cli.collectors = Some(parse(&arg, args.next())?),
+                "--map-width" => cli.map_width = Some(parse(&arg, args.next())?),
+                "--map-height" => cli.map_height = Some(parse(&arg, args.next())?),
+                "--duration" => cli.duration = Some(parse(&arg, args.next())?),
+                _ => return Err(CliError::UnknownArgument(arg)),
+            }
+        }
+
+        Ok(cli)
+    }
+
+    // Charge le fichier de configuration puis applique les surcharges
+    pub fn config(&self) -> Result<SimulationConfig, CliError> {
+        let mut config = match &self.config {
+            Some(path) => SimulationConfig::load(path).map_err(CliError::Config)?,
+            None if PathBuf::from(DEFAULT_CONFIG_PATH).exists() => {
+                SimulationConfig::load(DEFAULT_CONFIG_PATH).map_err(CliError::Config)?
+            }
+            None => SimulationConfig::default(),
+        };
+
+        if let Some(seed) = self.seed {
+            config.seed = seed;
+        }
+        if let Some(count) = self.explorers {
+            config.explorers.count = count;
+        }
+        if let Some(count) = self.collectors {
+            config.collectors.count = count;
+        }
+        if let Some(width) = self.map_width {
+            config.map.width = width;
+        }
+        if let Some(height) = self.map_height {
+            config.map.height = height;
+        }
+        if let Some(duration) = self.duration {
+            config.duration = Some(duration);
+        }
+
+        config.validate().map_err(CliError::Config)?;
+        Ok(config)
+    }
+}
+
+fn value(flag: &str, value: Option<String>) -> Result<String, CliError> {
+    value.ok_or_else(|| CliError::MissingValue(flag.to_string()))
+}
+
+fn parse<T: std::str::FromStr>(flag: &str, raw: Option<String>) -> Result<T, CliError> {
+    let raw = value(flag, raw)?;
+    raw.parse().map_err(|_| CliError::InvalidValue {
+        flag: flag.to_string(),
+        value: raw,
+    })
+}
@@ -0,0 +1,257 @@
+use crate::common::Statistics;
+use crate::config::SimulationConfig;
+use crate::map::Map;
+use crate::robot::Robot;
+use crate::spatial::RobotIndex;
+use crate::state::{BehaviourSet, RobotState};
+use crate::steering::{separation, Agent};
+use bevy::{
+    prelude::*,
+    utils::{HashMap, HashSet},
+};
+
+pub struct CollisionPlugin;
+
+impl Plugin for CollisionPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            FixedUpdate,
+            (
+                avoid_robots
+                    .after(BehaviourSet::Steer)
+                    .before(BehaviourSet::Move),
+                separate_robots.in_set(BehaviourSet::Resolve),
+            ),
+        );
+    }
+}
+
+// Un robot à quai ou hors service ne bouge pas : les autres le contournent
+pub fn is_fixed(state: &RobotState) -> bool {
+    matches!(state, RobotState::Charging | RobotState::Disabled)
+}
+
+// Chevauchement de deux cercles : direction de `a` vers `b` et profondeur,
+// `None` s'ils ne se touchent pas
+pub fn overlap(a: Vec2, radius_a: f32, b: Vec2, radius_b: f32) -> Option<(Vec2, f32)> {
+    let offset = b - a;
+    let depth = radius_a + radius_b - offset.length();
+    if depth <= 0.0 {
+        return None;
+    }
+    // Deux robots au même endroit sont séparés selon l'axe x
+    Some((offset.try_normalize().unwrap_or(Vec2::X), depth))
+}
+
+// Positions de départ de `count` robots de rayon au plus `radius` autour de `center`, sur une
+// grille assez espacée pour qu'ils ne se chevauchent pas, les plus proches d'abord. Les cases
+// bloquées et les bords de la carte sont évités ; s'il n'y a pas assez de place, les robots
+// restants partent de `center`
+pub fn spawn_spots(map: &Map, center: Vec2, radius: f32, count: usize) -> Vec<Vec2> {
+    let spacing = 2.0 * radius;
+    let extent = map.extent();
+    let max_ring = (extent.max_element() / spacing.max(1.0)).ceil() as i32;
+
+    let mut spots = vec![];
+    for ring in 0..=max_ring {
+        if spots.len() >= count {
+            break;
+        }
+        let mut ring_spots: Vec<Vec2> = (-ring..=ring)
+            .flat_map(|dy| (-ring..=ring).map(move |dx| IVec2::new(dx, dy)))
+            .filter(|offset| offset.x.abs() == ring || offset.y.abs() == ring)
+            .map(|offset| center + offset.as_vec2() * spacing)
+            .filter(|spot| {
+                spot.cmpge(Vec2::splat(radius)).all()
+                    && spot.cmple(extent - radius).all()
+                    && !map.circle_hits_obstacle(*spot, radius)
+            })
+            .collect();
+        ring_spots.sort_by(|a, b| {
+            a.distance_squared(center)
+                .total_cmp(&b.distance_squared(center))
+        });
+        spots.extend(ring_spots);
+    }
+
+    spots.truncate(count);
+    spots.resize(count, center);
+    spots
+}
+
+// Un robot se détourne des robots devant lui, à moins de `avoidance_range` de son bord
+fn avoid_robots(
+    mut robots: Query<(Entity, &Transform, &mut Robot, &RobotState)>,
+    robot_index: Res<RobotIndex>,
+    config: Res<SimulationConfig>,
+    time: Res<Time>,
+) {
+    let range = config.collisions.avoidance_range;
+    if !config.collisions.robots || range <= 0.0 {
+        return;
+    }
+    let radii: HashMap<Entity, f32> = robots
+        .iter()
+        .map(|(entity, _, robot, _)| (entity, robot.radius))
+        .collect();
+    let max_radius = radii.values().copied().fold(0.0, f32::max);
+
+    for (entity, transform, mut robot, state) in robots.iter_mut() {
+        if is_fixed(state) {
+            continue;
+        }
+        let position = transform.translation.truncate();
+        let heading = Vec2::from_angle(robot.direction);
+        let reach = robot.radius + max_radius + range;
+
+        let ahead: Vec<Vec2> = robot_index
+            .within_radius(position, reach)
+            .filter(|(other, other_position)| {
+                *other != entity
+                    && radii.get(other).is_some_and(|other_radius| {
+                        other_position.distance(position) < robot.radius + other_radius + range
+                    })
+                    && (*other_position - position).dot(heading) > 0.0
+            })
+            .map(|(_, other_position)| other_position)
+            .collect();
+        if ahead.is_empty() {
+            continue;
+        }
+
+        let agent = Agent::new(position, &robot);
+        let decision = agent.decide(
+            separation(&agent, &ahead, reach),
+            robot.max_turn_rate * time.delta_secs(),
+        );
+        if let Some(heading) = decision.heading {
+            robot.direction = heading;
+        }
+    }
+}
+
+// Écarte les robots qui se chevauchent après leur déplacement, à parts égales ou entièrement
+// pour celui qui peut bouger ; deux robots immobiles, par exemple à quai, s'écartent aussi.
+// Une correction qui ferait entrer un robot dans un obstacle est abandonnée. Les corrections
+// sont répétées tant qu'il reste des chevauchements, un robot poussé pouvant en heurter un autre
+#[allow(clippy::type_complexity)]
+fn separate_robots(
+    mut robots: Query<(Entity, &mut Transform, &Robot, &RobotState)>,
+    robot_index: Res<RobotIndex>,
+    map: Single<&Map>,
+    config: Res<SimulationConfig>,
+    time: Res<Time>,
+    mut statistics: ResMut<Statistics>,
+    mut contacts: Local<HashSet<(Entity, Entity)>>,
+) {
+    const MAX_ITERATIONS: usize = 16;
+
+    if !config.collisions.robots {
+        return;
+    }
+
+    // Position, rayon et immobilité de chaque robot, dans l'ordre de la requête
+    let mut bodies: Vec<(Entity, Vec2, f32, bool)> = robots
+        .iter()
+        .map(|(entity, transform, robot, state)| {
+            (
+                entity,
+                transform.translation.truncate(),
+                robot.radius,
+                is_fixed(state),
+            )
+        })
+        .collect();
+    let slots: HashMap<Entity, usize> = bodies
+        .iter()
+        .enumerate()
+        .map(|(slot, body)| (body.0, slot))
+        .collect();
+    let max_radius = bodies.iter().map(|body| body.2).fold(0.0, f32::max);
+    // L'index date du pas de temps précédent : chaque robot a pu avancer d'un pas depuis,
+    // puis être poussé d'autant
+    let max_speed = robots
+        .iter()
+        .map(|(_, _, robot, _)| robot.speed)
+        .fold(0.0, f32::max);
+    let slack = 4.0 * max_speed * time.delta_secs();
+
+    // Paires de robots assez proches pour se toucher, chacune une seule fois. Un robot qui
+    // vient d'apparaître n'est pas encore indexé : il est comparé à tous les autres
+    let mut pairs = vec![];
+    for (slot, &(entity, position, radius, _)) in bodies.iter().enumerate() {
+        if robot_index.position(entity).is_none() {
+            for (other_slot, &(other, ..)) in bodies.iter().enumerate() {
+                if other_slot != slot && (robot_index.position(other).is_some() || other > entity) {
+                    pairs.push((slot, other_slot));
+                }
+            }
+            continue;
+        }
+        for (other, _) in robot_index.within_radius(position, radius + max_radius + slack) {
+            let Some(&other_slot) = slots.get(&other) else {
+                continue;
+            };
+            if other > entity {
+                pairs.push((slot, other_slot));
+            }
+        }
+    }
+
+    let extent = map.extent();
+    let mut touching = HashSet::default();
+    for iteration in 0..MAX_ITERATIONS {
+        let mut corrections = vec![Vec2::ZERO; bodies.len()];
+        for &(a, b) in &pairs {
+            let (entity, position, radius, fixed) = bodies[a];
+            let (other, other_position, other_radius, other_fixed) = bodies[b];
+            let Some((normal, depth)) = overlap(position, radius, other_position, other_radius)
+            else {
+                continue;
+            };
+            if iteration == 0 {
+                touching.insert((entity, other));
+            }
+
+            let share = match (fixed, other_fixed) {
+                (true, false) => 0.0,
+                (false, true) => 1.0,
+                _ => 0.5,
+            };
+            corrections[a] -= normal * depth * share;
+            corrections[b] += normal * depth * (1.0 - share);
+        }
+        if corrections
+            .iter()
+            .all(|correction| *correction == Vec2::ZERO)
+        {
+            break;
+        }
+
+        for (body, correction) in bodies.iter_mut().zip(corrections) {
+            if correction == Vec2::ZERO {
+                continue;
+            }
+            let (_, position, radius, _) = *body;
+            let corrected = (position + correction).clamp(Vec2::splat(radius), extent - radius);
+            if map.is_blocked_at(corrected)
+                || (map.circle_hits_obstacle(corrected, radius)
+                    && !map.circle_hits_obstacle(position, radius))
+            {
+                continue;
+            }
+            body.1 = corrected;
+        }
+    }
+
+    statistics.robot_collisions += touching.difference(&contacts).count() as u32;
+    *contacts = touching;
+
+    for (entity, mut transform, ..) in robots.iter_mut() {
+        let position = bodies[slots[&entity]].1;
+        if transform.translation.truncate() != position {
+            transform.translation.x = position.x;
+            transform.translation.y = position.y;
+        }
+    }
+}
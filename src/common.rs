@@ -1,13 +1,23 @@
-use bevy::{math::bounding::Aabb2d, prelude::*};
+use crate::config::SimulationConfig;
+use crate::exploration::Coverage;
+use crate::flocking::SwarmMetrics;
+use bevy::{app::AppExit, math::bounding::Aabb2d, prelude::*, utils::HashMap};
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
 
 pub struct CommonPlugin;
 
 impl Plugin for CommonPlugin {
     fn build(&self, app: &mut App) {
         app.add_event::<ResourceCollectedEvent>();
+        app.init_resource::<SimulationConfig>();
+        app.add_systems(PreStartup, seed_rng);
         app.add_systems(Startup, setup);
+        app.add_systems(FixedUpdate, stop_after_duration);
         app.add_systems(Update, update_score);
+        app.add_systems(Last, print_summary);
         app.insert_resource(DiscoveredResources(vec![]));
+        app.init_resource::<Statistics>();
     }
 }
 
@@ -16,16 +26,27 @@ pub struct Collider {
     pub bounding_box: Aabb2d,
 }
 
-#[derive(Component)]
-pub struct Obstacle;
-
-#[derive(Component, Clone, Copy, PartialEq, Debug)]
+#[derive(Component, Clone, Copy, PartialEq, Eq, Hash, Debug)]
 pub enum ResourceType {
     Energy,
     Mineral,
     Scientific,
 }
+
+impl ResourceType {
+    pub const ALL: [ResourceType; 3] = [
+        ResourceType::Energy,
+        ResourceType::Mineral,
+        ResourceType::Scientific,
+    ];
+}
+// Base où les collecteurs déchargent leur cargaison
 #[derive(Component)]
+pub struct Base {
+    pub radius: f32,
+}
+
+#[derive(Component, Clone, Copy, Debug)]
 pub struct GameResource {
     pub kind: ResourceType,
     pub points: u32,
@@ -43,21 +64,159 @@ impl GameResource {
 #[derive(Resource)]
 pub struct Score(pub u32);
 
+// Envoyé lorsqu'une ressource est déchargée à la base
 #[derive(Event)]
 pub struct ResourceCollectedEvent {
+    pub kind: ResourceType,
     pub points: u32,
 }
 
+#[derive(Clone, PartialEq, Debug)]
+pub struct Discovery {
+    pub resource: Entity, // Entité de la ressource, qui l'identifie
+    pub position: Vec2,
+    pub claimed_by: Option<Entity>, // Collecteur en route vers la ressource
+    pub unreachable_for: Vec<Entity>, // Collecteurs n'ayant trouvé aucun chemin
+}
+
+#[derive(Resource, Clone, PartialEq, Debug)]
+pub struct DiscoveredResources(pub Vec<Discovery>);
+
+impl DiscoveredResources {
+    pub fn contains(&self, resource: Entity) -> bool {
+        self.0
+            .iter()
+            .any(|discovery| discovery.resource == resource)
+    }
+
+    pub fn get_mut(&mut self, resource: Entity) -> Option<&mut Discovery> {
+        self.0
+            .iter_mut()
+            .find(|discovery| discovery.resource == resource)
+    }
+
+    // Ajoute une ressource non encore connue, retourne `true` si elle a été ajoutée
+    pub fn insert(&mut self, resource: Entity, position: Vec2) -> bool {
+        if self.contains(resource) {
+            return false;
+        }
+        self.0.push(Discovery {
+            resource,
+            position,
+            claimed_by: None,
+            unreachable_for: vec![],
+        });
+        true
+    }
+
+    pub fn remove(&mut self, resource: Entity) {
+        self.0.retain(|discovery| discovery.resource != resource);
+    }
+
+    // Libère les ressources réservées par un robot
+    pub fn release(&mut self, robot: Entity) {
+        for discovery in self.0.iter_mut() {
+            if discovery.claimed_by == Some(robot) {
+                discovery.claimed_by = None;
+            }
+        }
+    }
+}
+
+// Bilan de la simulation, affiché à la fin de l'exécution
+#[derive(Resource, Default)]
+pub struct Statistics {
+    pub collected: HashMap<ResourceType, u32>,
+    pub discoveries: u32,
+    pub robots_disabled: u32,
+    pub messages_sent: u32, // Copies émises, une par destinataire à portée
+    pub messages_delivered: u32, // Copies reçues, une par destinataire
+    pub messages_dropped: u32, // Copies perdues (perte ou bande passante épuisée)
+    pub knowledge_merges: u32,
+    pub tasks_awarded: u32,    // Ressources attribuées aux enchères
+    pub tasks_reassigned: u32, // Ressources retirées à un collecteur pour une meilleure offre
+    pub robot_collisions: u32, // Contacts entre deux robots
+}
+
+// Unique source d'aléa de la simulation, dérivée de la graine de la configuration
 #[derive(Resource)]
-pub struct DiscoveredResources(pub Vec<Vec2>);
+pub struct SimulationRng(pub ChaCha8Rng);
+
+impl SimulationRng {
+    pub fn new(seed: u32) -> Self {
+        Self(ChaCha8Rng::seed_from_u64(seed as u64))
+    }
+}
+
+fn seed_rng(mut commands: Commands, config: Res<SimulationConfig>) {
+    commands.insert_resource(SimulationRng::new(config.seed));
+}
 
 fn setup(mut commands: Commands) {
     commands.insert_resource(Score(0));
 }
 
-fn update_score(mut score: ResMut<Score>, mut events: EventReader<ResourceCollectedEvent>) {
+fn update_score(
+    mut score: ResMut<Score>,
+    mut statistics: ResMut<Statistics>,
+    mut events: EventReader<ResourceCollectedEvent>,
+) {
     for e in events.read() {
         score.0 += e.points;
+        *statistics.collected.entry(e.kind).or_default() += 1;
         info!("Resource collected!. Score: {}", score.0);
     }
 }
+
+fn stop_after_duration(
+    time: Res<Time>,
+    config: Res<SimulationConfig>,
+    mut exit: EventWriter<AppExit>,
+) {
+    if let Some(duration) = config.duration {
+        if time.elapsed_secs() >= duration {
+            exit.send(AppExit::Success);
+        }
+    }
+}
+
+fn print_summary(
+    mut exit_events: EventReader<AppExit>,
+    score: Res<Score>,
+    statistics: Res<Statistics>,
+    coverage: Option<Res<Coverage>>,
+    swarm: Option<Res<SwarmMetrics>>,
+    time: Res<Time<Fixed>>,
+) {
+    if exit_events.read().next().is_none() {
+        return;
+    }
+
+    println!("Simulated time: {:.2}s", time.elapsed_secs());
+    println!("Score: {}", score.0);
+    for kind in ResourceType::ALL {
+        let collected = statistics.collected.get(&kind).copied().unwrap_or(0);
+        println!("Collected {kind:?}: {collected}");
+    }
+    println!("Discoveries: {}", statistics.discoveries);
+    if let Some(coverage) = coverage {
+        println!("Coverage: {:.1}%", coverage.0);
+    }
+    println!("Robots disabled: {}", statistics.robots_disabled);
+    println!("Robot collisions: {}", statistics.robot_collisions);
+    println!(
+        "Messages: {} sent, {} delivered, {} dropped",
+        statistics.messages_sent, statistics.messages_delivered, statistics.messages_dropped
+    );
+    println!("Knowledge merges: {}", statistics.knowledge_merges);
+    println!(
+        "Auctions: {} awarded, {} reassigned",
+        statistics.tasks_awarded, statistics.tasks_reassigned
+    );
+    if let Some(swarm) = swarm {
+        println!(
+            "Swarm: polarisation {:.2}, cohesion {:.1}",
+            swarm.mean_polarisation, swarm.mean_cohesion
+        );
+    }
+}
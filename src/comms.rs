@@ -0,0 +1,177 @@
+use crate::common::{ResourceType, SimulationRng, Statistics};
+use crate::config::SimulationConfig;
+use crate::energy::Disabled;
+use crate::knowledge::Knowledge;
+use crate::robot::Robot;
+use crate::spatial::{RobotIndex, SpatialIndexSet};
+use bevy::{prelude::*, utils::HashMap};
+use rand::Rng;
+use std::sync::Arc;
+use std::time::Duration;
+
+pub struct CommsPlugin;
+
+impl Plugin for CommsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<OutgoingMessage>();
+        app.add_event::<IncomingMessage>();
+        app.init_resource::<InFlightMessages>();
+        app.add_systems(
+            FixedUpdate,
+            (broadcast_positions, transmit_messages, deliver_messages)
+                .chain()
+                .in_set(MessagingSet)
+                .after(SpatialIndexSet),
+        );
+    }
+}
+
+// Les messages émis avant ce set sont distribués dans le même pas de temps
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MessagingSet;
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum RobotMessage {
+    Discovery {
+        resource: Entity,
+        position: Vec2,
+        kind: ResourceType,
+    },
+    Claim {
+        resource: Entity,
+    },
+    HelpRequest, // L'émetteur est hors service à la position d'émission
+    // Annonce de position, avec la connaissance de l'émetteur au moment de l'envoi s'il en a une
+    Position(Option<Arc<Knowledge>>),
+}
+
+// Message diffusé par un robot, reçu par les robots à portée de `position`
+#[derive(Event, Clone, Debug)]
+pub struct OutgoingMessage {
+    pub sender: Entity,
+    pub position: Vec2,
+    pub message: RobotMessage,
+}
+
+// Un événement par destinataire
+#[derive(Event, Clone, Debug)]
+pub struct IncomingMessage {
+    pub receiver: Entity,
+    pub sender: Entity,
+    pub sender_position: Vec2,
+    pub message: RobotMessage,
+}
+
+// Copies en cours d'acheminement, avec le temps simulé de leur réception
+#[derive(Resource, Default)]
+pub struct InFlightMessages(pub Vec<(Duration, IncomingMessage)>);
+
+// Chaque robot en service annonce régulièrement sa position
+#[allow(clippy::type_complexity)]
+fn broadcast_positions(
+    robots: Query<(Entity, &Transform, Option<&Knowledge>), (With<Robot>, Without<Disabled>)>,
+    config: Res<SimulationConfig>,
+    time: Res<Time>,
+    mut elapsed: Local<f32>,
+    mut outgoing: EventWriter<OutgoingMessage>,
+) {
+    *elapsed += time.delta_secs();
+    if *elapsed < config.comms.position_interval {
+        return;
+    }
+    *elapsed -= config.comms.position_interval;
+
+    for (entity, transform, knowledge) in &robots {
+        outgoing.send(OutgoingMessage {
+            sender: entity,
+            position: transform.translation.truncate(),
+            message: RobotMessage::Position(knowledge.cloned().map(Arc::new)),
+        });
+    }
+}
+
+// Applique le modèle de liaison à chaque copie d'un message diffusé :
+// bande passante de l'émetteur, perte puis délai avant réception
+#[allow(clippy::too_many_arguments)]
+fn transmit_messages(
+    mut outgoing: EventReader<OutgoingMessage>,
+    mut in_flight: ResMut<InFlightMessages>,
+    receivers: Query<(), (With<Robot>, Without<Disabled>)>,
+    robot_index: Res<RobotIndex>,
+    config: Res<SimulationConfig>,
+    time: Res<Time>,
+    mut rng: ResMut<SimulationRng>,
+    mut statistics: ResMut<Statistics>,
+) {
+    let comms = &config.comms;
+    let rng = &mut rng.0;
+    let mut transmitted: HashMap<Entity, u32> = HashMap::default();
+
+    for sent in outgoing.read() {
+        // Ordre stable des destinataires pour garder la simulation reproductible
+        let mut in_range: Vec<Entity> = robot_index
+            .within_radius(sent.position, comms.range)
+            .map(|(entity, _)| entity)
+            .filter(|&entity| entity != sent.sender && receivers.contains(entity))
+            .collect();
+        in_range.sort();
+        statistics.messages_sent += in_range.len() as u32;
+
+        // Au-delà de son budget, l'émetteur ne diffuse plus rien pendant ce pas de temps
+        let count = transmitted.entry(sent.sender).or_default();
+        *count += 1;
+        if comms.bandwidth.is_some_and(|bandwidth| *count > bandwidth) {
+            statistics.messages_dropped += in_range.len() as u32;
+            continue;
+        }
+
+        for receiver in in_range {
+            if comms.loss > 0.0 && rng.gen_bool(comms.loss as f64) {
+                statistics.messages_dropped += 1;
+                continue;
+            }
+
+            let mut latency = comms.latency;
+            if comms.latency_jitter > 0.0 {
+                latency += rng.gen_range(0.0..comms.latency_jitter);
+            }
+
+            in_flight.0.push((
+                time.elapsed()
+                    .saturating_add(Duration::try_from_secs_f32(latency).unwrap_or(Duration::MAX)),
+                IncomingMessage {
+                    receiver,
+                    sender: sent.sender,
+                    sender_position: sent.position,
+                    message: sent.message.clone(),
+                },
+            ));
+        }
+    }
+}
+
+// Remet les messages arrivés à échéance, dans leur ordre d'émission
+fn deliver_messages(
+    mut in_flight: ResMut<InFlightMessages>,
+    mut incoming: EventWriter<IncomingMessage>,
+    receivers: Query<(), (With<Robot>, Without<Disabled>)>,
+    time: Res<Time>,
+    mut statistics: ResMut<Statistics>,
+) {
+    let now = time.elapsed();
+
+    in_flight.0.retain(|(deliver_at, message)| {
+        if *deliver_at > now {
+            return true;
+        }
+
+        // Un robot éteint entre-temps ne reçoit plus rien
+        if receivers.contains(message.receiver) {
+            incoming.send(message.clone());
+            statistics.messages_delivered += 1;
+        } else {
+            statistics.messages_dropped += 1;
+        }
+        false
+    });
+}
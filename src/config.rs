@@ -0,0 +1,550 @@
+use crate::behaviour_tree::Node;
+use crate::map::{TerrainKind, TerrainThreshold};
+use crate::steering::Steering;
+use bevy::prelude::*;
+use serde::Deserialize;
+use std::{collections::HashMap, fmt, fs, path::Path};
+
+// Paramètres de la simulation, chargés depuis un fichier RON.
+// Tout champ absent du fichier garde sa valeur par défaut.
+#[derive(Resource, Deserialize, Clone, Debug)]
+#[serde(default, deny_unknown_fields)]
+pub struct SimulationConfig {
+    pub seed: u32,
+    pub duration: Option<f32>, // Durée simulée en secondes, illimitée si absente
+    pub map: MapConfig,
+    pub window: WindowConfig,
+    pub resources: ResourcesConfig,
+    pub base: BaseConfig,
+    pub explorers: RobotConfig,
+    pub collectors: RobotConfig,
+    pub battery: BatteryConfig,
+    pub comms: CommsConfig,
+    pub knowledge: KnowledgeModel,
+    pub exploration: ExplorationConfig,
+    pub allocation: AllocationConfig,
+    pub behaviours: HashMap<String, Node>, // Arbres de comportement, par rôle
+    pub flocking: HashMap<String, FlockingConfig>, // Règles de l'essaim, par rôle
+    pub steering: HashMap<String, Vec<(f32, Steering)>>, // Comportements pondérés, par rôle
+    pub pathfinding: PathfindingConfig,
+    pub collisions: CollisionConfig,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+#[serde(default, deny_unknown_fields)]
+pub struct MapConfig {
+    pub width: u32,  // Nombre de tuiles sur l'axe x
+    pub height: u32, // Nombre de tuiles sur l'axe y
+    pub scale: f64,
+    pub tile_size: u32,
+    pub terrain: Vec<TerrainThreshold>,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+#[serde(default, deny_unknown_fields)]
+pub struct WindowConfig {
+    pub width: f32,
+    pub height: f32,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+#[serde(default, deny_unknown_fields)]
+pub struct ResourcesConfig {
+    pub count: usize,
+    pub size: f32,
+}
+
+// La base est placée sur la case praticable la plus proche de `cell`
+#[derive(Deserialize, Clone, Debug)]
+#[serde(default, deny_unknown_fields)]
+pub struct BaseConfig {
+    pub cell: (u32, u32),
+    pub radius: f32,
+}
+
+// Les coûts sont en unités de charge ; la base sert de point de recharge
+#[derive(Deserialize, Clone, Debug)]
+#[serde(default, deny_unknown_fields)]
+pub struct BatteryConfig {
+    pub enabled: bool, // Sans batterie, les robots ne s'épuisent jamais
+    pub capacity: f32,
+    pub move_cost: f32,             // Par unité de distance parcourue
+    pub sense_cost: f32,            // Par seconde
+    pub carry_cost: f32,            // Par ressource transportée et par seconde
+    pub low_threshold: f32, // Fraction de la capacité sous laquelle le robot rentre se recharger
+    pub charge_rate: f32,   // Par seconde à la base
+    pub energy_pickup: Option<f32>, // Charge rendue par une ressource Energy, qui n'est alors plus rapportée
+}
+
+#[derive(Deserialize, Clone, Debug)]
+#[serde(default, deny_unknown_fields)]
+pub struct CommsConfig {
+    pub range: f32,             // Portée de communication entre robots, en unités du monde
+    pub position_interval: f32, // Secondes entre deux annonces de position
+    pub latency: f32,           // Délai de chaque liaison, en secondes
+    pub latency_jitter: f32,    // Délai supplémentaire aléatoire, entre 0 et cette valeur
+    pub loss: f32,              // Probabilité de perte d'une copie du message
+    pub bandwidth: Option<u32>, // Messages diffusés par robot et par pas de temps, illimité si absent
+}
+
+// Partage des ressources découvertes entre les robots
+#[derive(Deserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum KnowledgeModel {
+    #[default]
+    Shared, // Une liste commune, connue instantanément de tous
+    Local, // Chaque robot a sa propre connaissance, fusionnée à portée de communication ou à la base
+}
+
+#[derive(Deserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum ExplorationStrategy {
+    #[default]
+    RandomWalk,
+    Frontier, // Les explorateurs se dirigent vers les limites de la zone explorée
+}
+
+#[derive(Deserialize, Clone, Debug)]
+#[serde(default, deny_unknown_fields)]
+pub struct ExplorationConfig {
+    pub strategy: ExplorationStrategy,
+    pub min_frontier_size: usize, // Nombre de cases en dessous duquel un groupe de frontières est ignoré
+    pub spread: f32, // Distance minimale entre les frontières visées par deux explorateurs
+}
+
+#[derive(Deserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum AllocationStrategy {
+    #[default]
+    Nearest, // Chaque collecteur réserve la ressource la plus proche
+    Auction, // Les ressources sont mises aux enchères, le collecteur le moins coûteux l'emporte
+}
+
+#[derive(Deserialize, Clone, Debug)]
+#[serde(default, deny_unknown_fields)]
+pub struct AllocationConfig {
+    pub strategy: AllocationStrategy,
+    pub reassign_margin: f32, // Secondes de trajet à gagner pour retirer une ressource à son collecteur
+}
+
+#[derive(Deserialize, Clone, Debug, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct PathfindingConfig {
+    pub terrain_cost: bool, // Pondérer les chemins par le coût du terrain
+}
+
+// Collisions entre robots
+#[derive(Deserialize, Clone, Debug)]
+#[serde(default, deny_unknown_fields)]
+pub struct CollisionConfig {
+    pub robots: bool,         // Les robots ne peuvent pas se chevaucher
+    pub avoidance_range: f32, // Distance entre bords à laquelle un robot s'écarte d'un autre, 0 pour ne pas s'écarter
+}
+
+// Une section de robots, si présente, doit être complète
+#[derive(Deserialize, Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct RobotConfig {
+    pub count: usize,
+    pub radius: f32,
+    pub speed: f32,
+    pub max_turn_rate: f32,
+    pub max_deviation: f32, // En radians
+    pub sensor_range: u32,
+    #[serde(default)]
+    pub capacity: u32, // Nombre de ressources transportables, 0 pour un robot qui ne collecte pas
+    #[serde(default)]
+    pub role: Option<String>, // Rôle des robots, « Explorer » ou « Collector » si absent
+    #[serde(default)]
+    pub strategy: Option<String>, // Stratégie de déplacement, « RandomWalk » ou « SeekNearest » si absent
+}
+
+// Règles de l'essaim, pour les robots dont la stratégie est « Flocking »
+#[derive(Deserialize, Clone, Debug)]
+#[serde(default, deny_unknown_fields)]
+pub struct FlockingConfig {
+    pub range: f32,            // Distance à laquelle un robot perçoit ses voisins
+    pub separation_range: f32, // Distance en dessous de laquelle un voisin est trop proche
+    pub separation: f32,       // Poids de chaque règle
+    pub alignment: f32,
+    pub cohesion: f32,
+}
+
+impl Default for SimulationConfig {
+    fn default() -> Self {
+        Self {
+            seed: 0,
+            duration: None,
+            map: MapConfig::default(),
+            window: WindowConfig::default(),
+            resources: ResourcesConfig::default(),
+            base: BaseConfig::default(),
+            explorers: RobotConfig {
+                count: 2,
+                radius: 5.0,
+                speed: 70.0,
+                max_turn_rate: 5.0,
+                max_deviation: std::f32::consts::FRAC_PI_2,
+                sensor_range: 200,
+                capacity: 0,
+                role: None,
+                strategy: None,
+            },
+            collectors: RobotConfig {
+                count: 2,
+                radius: 10.0,
+                speed: 50.0,
+                max_turn_rate: 10.0,
+                max_deviation: std::f32::consts::FRAC_PI_2,
+                sensor_range: 100,
+                capacity: 3,
+                role: None,
+                strategy: None,
+            },
+            battery: BatteryConfig::default(),
+            comms: CommsConfig::default(),
+            knowledge: KnowledgeModel::default(),
+            exploration: ExplorationConfig::default(),
+            allocation: AllocationConfig::default(),
+            behaviours: HashMap::new(),
+            flocking: HashMap::new(),
+            steering: HashMap::new(),
+            pathfinding: PathfindingConfig::default(),
+            collisions: CollisionConfig::default(),
+        }
+    }
+}
+
+impl Default for FlockingConfig {
+    fn default() -> Self {
+        Self {
+            range: 80.0,
+            separation_range: 30.0,
+            separation: 1.5,
+            alignment: 1.0,
+            cohesion: 1.0,
+        }
+    }
+}
+
+impl Default for MapConfig {
+    fn default() -> Self {
+        Self {
+            width: 250,
+            height: 250,
+            scale: 15.0,
+            tile_size: 16,
+            terrain: vec![
+                TerrainThreshold {
+                    kind: TerrainKind::Rock,
+                    above: 0.2,
+                },
+                TerrainThreshold {
+                    kind: TerrainKind::Sand,
+                    above: 0.35,
+                },
+                TerrainThreshold {
+                    kind: TerrainKind::Dune,
+                    above: 0.65,
+                },
+                TerrainThreshold {
+                    kind: TerrainKind::Highland,
+                    above: 0.75,
+                },
+            ],
+        }
+    }
+}
+
+impl Default for WindowConfig {
+    fn default() -> Self {
+        Self {
+            width: 1000.0,
+            height: 800.0,
+        }
+    }
+}
+
+impl Default for BaseConfig {
+    fn default() -> Self {
+        Self {
+            cell: (2, 2),
+            radius: 24.0,
+        }
+    }
+}
+
+impl Default for BatteryConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            capacity: 100.0,
+            move_cost: 0.005,
+            sense_cost: 0.02,
+            carry_cost: 0.01,
+            low_threshold: 0.25,
+            charge_rate: 20.0,
+            energy_pickup: None,
+        }
+    }
+}
+
+impl Default for CommsConfig {
+    fn default() -> Self {
+        Self {
+            range: 150.0,
+            position_interval: 1.0,
+            latency: 0.0,
+            latency_jitter: 0.0,
+            loss: 0.0,
+            bandwidth: None,
+        }
+    }
+}
+
+impl Default for ExplorationConfig {
+    fn default() -> Self {
+        Self {
+            strategy: ExplorationStrategy::default(),
+            min_frontier_size: 4,
+            spread: 200.0,
+        }
+    }
+}
+
+impl Default for AllocationConfig {
+    fn default() -> Self {
+        Self {
+            strategy: AllocationStrategy::default(),
+            reassign_margin: 2.0,
+        }
+    }
+}
+
+impl Default for CollisionConfig {
+    fn default() -> Self {
+        Self {
+            robots: true,
+            avoidance_range: 10.0,
+        }
+    }
+}
+
+impl Default for ResourcesConfig {
+    fn default() -> Self {
+        Self {
+            count: 300,
+            size: 8.0,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum ConfigError {
+    Io(std::io::Error),
+    Parse(ron::error::SpannedError),
+    Invalid { field: String, reason: String },
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::Io(error) => write!(f, "cannot read config file: {error}"),
+            ConfigError::Parse(error) => write!(f, "cannot parse config file: {error}"),
+            ConfigError::Invalid { field, reason } => {
+                write!(f, "invalid value for `{field}`: {reason}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+impl SimulationConfig {
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, ConfigError> {
+        let content = fs::read_to_string(path).map_err(ConfigError::Io)?;
+        Self::parse(&content)
+    }
+
+    pub fn parse(content: &str) -> Result<Self, ConfigError> {
+        let config: Self = ron::from_str(content).map_err(ConfigError::Parse)?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        if let Some(duration) = self.duration {
+            positive("duration", duration as f64)?;
+        }
+        positive("map.width", self.map.width as f64)?;
+        positive("map.height", self.map.height as f64)?;
+        positive("map.scale", self.map.scale)?;
+        positive("map.tile_size", self.map.tile_size as f64)?;
+        for (i, threshold) in self.map.terrain.iter().enumerate() {
+            if !threshold.above.is_finite() {
+                return Err(invalid(
+                    &format!("map.terrain[{i}].above"),
+                    "must be a finite number",
+                ));
+            }
+        }
+        positive("window.width", self.window.width as f64)?;
+        positive("window.height", self.window.height as f64)?;
+        positive("resources.size", self.resources.size as f64)?;
+
+        // Une ressource doit pouvoir tenir entièrement dans la carte
+        let map_extent = self.map.width.min(self.map.height) as f32 * self.map.tile_size as f32;
+        if self.resources.size * 2.0 >= map_extent {
+            return Err(invalid("resources.size", "larger than the map"));
+        }
+
+        positive("base.radius", self.base.radius as f64)?;
+        if self.base.cell.0 >= self.map.width || self.base.cell.1 >= self.map.height {
+            return Err(invalid("base.cell", "outside the map"));
+        }
+
+        positive("battery.capacity", self.battery.capacity as f64)?;
+        not_negative("battery.move_cost", self.battery.move_cost as f64)?;
+        not_negative("battery.sense_cost", self.battery.sense_cost as f64)?;
+        not_negative("battery.carry_cost", self.battery.carry_cost as f64)?;
+        not_negative("battery.low_threshold", self.battery.low_threshold as f64)?;
+        if self.battery.low_threshold >= 1.0 {
+            return Err(invalid("battery.low_threshold", "must be lower than 1"));
+        }
+        positive("battery.charge_rate", self.battery.charge_rate as f64)?;
+        if let Some(charge) = self.battery.energy_pickup {
+            positive("battery.energy_pickup", charge as f64)?;
+        }
+
+        positive("comms.range", self.comms.range as f64)?;
+        positive(
+            "comms.position_interval",
+            self.comms.position_interval as f64,
+        )?;
+        not_negative("comms.latency", self.comms.latency as f64)?;
+        not_negative("comms.latency_jitter", self.comms.latency_jitter as f64)?;
+        if !(0.0..=1.0).contains(&self.comms.loss) {
+            return Err(invalid("comms.loss", "must be between 0 and 1"));
+        }
+        if let Some(bandwidth) = self.comms.bandwidth {
+            positive("comms.bandwidth", bandwidth as f64)?;
+        }
+
+        positive(
+            "exploration.min_frontier_size",
+            self.exploration.min_frontier_size as f64,
+        )?;
+        not_negative("exploration.spread", self.exploration.spread as f64)?;
+        not_negative(
+            "allocation.reassign_margin",
+            self.allocation.reassign_margin as f64,
+        )?;
+
+        not_negative(
+            "collisions.avoidance_range",
+            self.collisions.avoidance_range as f64,
+        )?;
+
+        for (role, tree) in &self.behaviours {
+            tree.check()
+                .map_err(|reason| invalid(&format!("behaviours.{role}"), reason))?;
+        }
+        for (section, robots) in [
+            ("explorers", &self.explorers),
+            ("collectors", &self.collectors),
+        ] {
+            if let Some(role) = &robots.role {
+                if !self.behaviours.contains_key(role) {
+                    return Err(invalid(
+                        &format!("{section}.role"),
+                        "no behaviour tree is defined for this role",
+                    ));
+                }
+            }
+        }
+
+        self.explorers.validate("explorers")?;
+        self.collectors.validate("collectors")?;
+        for (role, flocking) in &self.flocking {
+            flocking.validate(&format!("flocking.{role}"))?;
+        }
+        for (role, behaviours) in &self.steering {
+            for (weight, behaviour) in behaviours {
+                not_negative(&format!("steering.{role}"), *weight as f64)?;
+                match behaviour {
+                    Steering::ArriveResource(radius)
+                    | Steering::FleeNeighbour(radius)
+                    | Steering::EvadeNeighbour(radius)
+                    | Steering::Separation(radius)
+                    | Steering::ObstacleAvoidance { look_ahead: radius } => {
+                        positive(&format!("steering.{role}"), *radius as f64)?;
+                    }
+                    _ => {}
+                }
+            }
+        }
+        if self.collectors.count > 0 && self.collectors.capacity == 0 {
+            return Err(invalid(
+                "collectors.capacity",
+                "collectors must be able to carry at least one resource",
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+impl RobotConfig {
+    fn validate(&self, section: &str) -> Result<(), ConfigError> {
+        positive(&format!("{section}.radius"), self.radius as f64)?;
+        not_negative(&format!("{section}.speed"), self.speed as f64)?;
+        positive(
+            &format!("{section}.max_turn_rate"),
+            self.max_turn_rate as f64,
+        )?;
+        positive(
+            &format!("{section}.max_deviation"),
+            self.max_deviation as f64,
+        )?;
+        positive(&format!("{section}.sensor_range"), self.sensor_range as f64)?;
+        Ok(())
+    }
+}
+
+impl FlockingConfig {
+    fn validate(&self, section: &str) -> Result<(), ConfigError> {
+        positive(&format!("{section}.range"), self.range as f64)?;
+        positive(
+            &format!("{section}.separation_range"),
+            self.separation_range as f64,
+        )?;
+        for (rule, weight) in [
+            ("separation", self.separation),
+            ("alignment", self.alignment),
+            ("cohesion", self.cohesion),
+        ] {
+            not_negative(&format!("{section}.{rule}"), weight as f64)?;
+        }
+        Ok(())
+    }
+}
+
+fn invalid(field: &str, reason: &str) -> ConfigError {
+    ConfigError::Invalid {
+        field: field.to_string(),
+        reason: reason.to_string(),
+    }
+}
+
+fn positive(field: &str, value: f64) -> Result<(), ConfigError> {
+    if value.is_finite() && value > 0.0 {
+        Ok(())
+    } else {
+        Err(invalid(field, "must be a positive number"))
+    }
+}
+
+fn not_negative(field: &str, value: f64) -> Result<(), ConfigError> {
+    if value.is_finite() && value >= 0.0 {
+        Ok(())
+    } else {
+        Err(invalid(field, "must not be negative"))
+    }
+}
@@ -0,0 +1,211 @@
+use crate::common::{Base, Collider, GameResource, ResourceType};
+use crate::config::SimulationConfig;
+use crate::energy::Disabled;
+use crate::exploration::CellsExplored;
+use crate::map::Map;
+use crate::robot::{Robot, Sensor};
+use bevy::math::{bounding::BoundingVolume, ivec3, uvec2};
+use bevy::prelude::*;
+use bevy_simple_tilemap::{Tile, TileMap};
+
+// Rendu de la simulation : fenêtre, caméra, tuiles et maillages des entités.
+// Les plugins de simulation n'ajoutent aucun composant graphique, ce plugin
+// les complète lorsqu'une fenêtre est disponible
+pub struct DisplayPlugin;
+
+impl Plugin for DisplayPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, setup);
+        app.add_systems(
+            Update,
+            (
+                draw_map,
+                reveal_explored.after(draw_map),
+                draw_base,
+                draw_resources,
+                draw_robots,
+                draw_disabled_robots.after(draw_robots),
+                draw_sensors,
+                update_sensor_material,
+            ),
+        );
+    }
+}
+
+#[derive(Resource)]
+pub struct SensorMaterial {
+    on: Handle<ColorMaterial>,
+    detected: Handle<ColorMaterial>,
+}
+
+fn setup(
+    mut commands: Commands,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    mut window: Single<&mut Window>,
+    config: Res<SimulationConfig>,
+) {
+    let (window_width, window_height) = (config.window.width, config.window.height);
+
+    window.resolution.set(window_width, window_height);
+    window.title = String::from("Robots Exploration");
+    window.resizable = false;
+
+    commands.spawn((
+        Camera2d,
+        Transform::from_xyz(window_width / 2.0, window_height / 2.0, 0.0),
+    ));
+
+    commands.insert_resource(SensorMaterial {
+        on: materials.add(Color::hsla(207.0, 1.9, 0.5, 0.2)),
+        detected: materials.add(Color::hsla(105.0, 0.55, 0.48, 0.2)),
+    });
+}
+
+fn draw_map(
+    mut commands: Commands,
+    map_query: Query<(Entity, &Map), Added<Map>>,
+    asset_server: Res<AssetServer>,
+    mut texture_atlas_layouts: ResMut<Assets<TextureAtlasLayout>>,
+) {
+    for (entity, map) in &map_query {
+        let texture_handle = asset_server.load::<Image>("tile.png");
+        let atlas_layout =
+            TextureAtlasLayout::from_grid(UVec2::splat(map.tile_size), 10, 10, None, None);
+        let mut tile_map = TileMap::new(texture_handle, texture_atlas_layouts.add(atlas_layout));
+
+        for y in 0..map.height {
+            for x in 0..map.width {
+                tile_map.set_tile(ivec3(x as i32, y as i32, 0), Some(tile(map, uvec2(x, y))));
+            }
+        }
+
+        commands.entity(entity).insert(tile_map);
+    }
+}
+
+// Brouillard de guerre : les cases encore jamais vues sont assombries
+fn tile(map: &Map, cell: UVec2) -> Tile {
+    Tile {
+        sprite_index: map
+            .terrain(cell)
+            .map_or(0, |terrain| terrain.sprite_index()),
+        color: if map.is_explored(cell) {
+            Color::WHITE
+        } else {
+            Color::srgb(0.25, 0.25, 0.3)
+        },
+        ..Default::default()
+    }
+}
+
+fn reveal_explored(
+    mut explored: EventReader<CellsExplored>,
+    mut map_query: Query<(&Map, &mut TileMap)>,
+) {
+    let Ok((map, mut tile_map)) = map_query.get_single_mut() else {
+        return;
+    };
+
+    for CellsExplored(cells) in explored.read() {
+        tile_map.set_tiles(cells.iter().map(|&cell| {
+            (
+                ivec3(cell.x as i32, cell.y as i32, 0),
+                Some(tile(map, cell)),
+            )
+        }));
+    }
+}
+
+fn draw_base(
+    mut commands: Commands,
+    base_query: Query<(Entity, &Base), Added<Base>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    for (entity, base) in &base_query {
+        commands.entity(entity).insert((
+            Mesh2d(meshes.add(Annulus::new(base.radius - 3.0, base.radius))),
+            MeshMaterial2d(materials.add(Color::srgb(0.9, 0.1, 0.1))),
+        ));
+    }
+}
+
+fn draw_resources(
+    mut commands: Commands,
+    resources_query: Query<(Entity, &GameResource, &Collider), Added<GameResource>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    for (entity, resource, collider) in &resources_query {
+        let size = collider.bounding_box.half_size().x;
+        let (color, mesh) = match resource.kind {
+            ResourceType::Energy => (Color::srgb(1.0, 1.0, 0.0), meshes.add(Circle::new(size))),
+            ResourceType::Mineral => (
+                Color::srgb(0.5, 0.5, 0.5),
+                meshes.add(Rectangle::new(size * 2.0, size * 2.0)),
+            ),
+            ResourceType::Scientific => (
+                Color::srgb(0.0, 0.0, 1.0),
+                meshes.add(RegularPolygon::new(size, 3)),
+            ),
+        };
+
+        commands
+            .entity(entity)
+            .insert((Mesh2d(mesh), MeshMaterial2d(materials.add(color))));
+    }
+}
+
+fn draw_robots(
+    mut commands: Commands,
+    robots_query: Query<(Entity, &Robot), Added<Robot>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    for (entity, robot) in &robots_query {
+        commands.entity(entity).insert((
+            Mesh2d(meshes.add(Circle::new(robot.radius))),
+            MeshMaterial2d(materials.add(Color::hsla(0.0, 0.0, 0.0, 1.0))),
+        ));
+    }
+}
+
+// Un robot hors service est grisé
+fn draw_disabled_robots(
+    mut commands: Commands,
+    robots_query: Query<Entity, Added<Disabled>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    for entity in &robots_query {
+        commands
+            .entity(entity)
+            .insert(MeshMaterial2d(materials.add(Color::srgb(0.6, 0.6, 0.6))));
+    }
+}
+
+fn draw_sensors(
+    mut commands: Commands,
+    sensors_query: Query<(Entity, &Sensor), Added<Sensor>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    sensor_material: Res<SensorMaterial>,
+) {
+    for (entity, sensor) in &sensors_query {
+        commands.entity(entity).insert((
+            Mesh2d(meshes.add(Circle::new(sensor.range as f32))),
+            MeshMaterial2d(sensor_material.on.clone()),
+        ));
+    }
+}
+
+fn update_sensor_material(
+    mut sensors_query: Query<(&Sensor, &mut MeshMaterial2d<ColorMaterial>), Changed<Sensor>>,
+    sensor_material: Res<SensorMaterial>,
+) {
+    for (sensor, mut material) in sensors_query.iter_mut() {
+        *material = MeshMaterial2d(if sensor.detecting {
+            sensor_material.detected.clone()
+        } else {
+            sensor_material.on.clone()
+        });
+    }
+}
@@ -0,0 +1,178 @@
+use crate::common::{Base, Statistics};
+use crate::comms::{OutgoingMessage, RobotMessage};
+use crate::config::SimulationConfig;
+use crate::knowledge::KnownResources;
+use crate::pathfinding::{PathFollower, PathfindingSet};
+use crate::robot::{Cargo, Returning, Robot, Target};
+use bevy::prelude::*;
+
+pub struct EnergyPlugin;
+
+impl Plugin for EnergyPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            FixedUpdate,
+            (drain_battery, disable_empty, seek_charger, charge_at_base)
+                .chain()
+                .in_set(EnergySet)
+                .before(PathfindingSet),
+        );
+    }
+}
+
+// Consommation et recharge des batteries, avant les décisions des robots
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct EnergySet;
+
+#[derive(Component)]
+pub struct Battery {
+    pub capacity: f32,
+    pub charge: f32,
+}
+
+impl Battery {
+    pub fn new(capacity: f32) -> Self {
+        Self {
+            capacity,
+            charge: capacity,
+        }
+    }
+
+    pub fn level(&self) -> f32 {
+        self.charge / self.capacity
+    }
+
+    pub fn recharge(&mut self, amount: f32) {
+        self.charge = (self.charge + amount).min(self.capacity);
+    }
+}
+
+// Robot à la batterie vide : il ne bouge plus, ne détecte plus et ne collecte plus
+#[derive(Component)]
+pub struct Disabled;
+
+// Robot en route vers la base pour se recharger, `docked` une fois arrivé
+#[derive(Component)]
+pub struct Recharging {
+    pub docked: bool,
+}
+
+#[allow(clippy::type_complexity)]
+fn drain_battery(
+    mut robots: Query<
+        (&Robot, &mut Battery, Option<&Cargo>, Option<&Recharging>),
+        Without<Disabled>,
+    >,
+    config: Res<SimulationConfig>,
+    time: Res<Time>,
+) {
+    let battery = &config.battery;
+    let delta = time.delta_secs();
+
+    for (robot, mut robot_battery, cargo, recharging) in robots.iter_mut() {
+        let moving = !recharging.is_some_and(|recharging| recharging.docked);
+        let carried = cargo.map_or(0, |cargo| cargo.items.len()) as f32;
+
+        let mut cost = battery.sense_cost * delta + battery.carry_cost * carried * delta;
+        if moving {
+            cost += battery.move_cost * robot.speed * robot.throttle * delta;
+        }
+
+        robot_battery.charge = (robot_battery.charge - cost).max(0.0);
+    }
+}
+
+fn disable_empty(
+    mut commands: Commands,
+    robots: Query<(Entity, &Transform, &Battery), Without<Disabled>>,
+    mut known: KnownResources,
+    mut statistics: ResMut<Statistics>,
+    mut outgoing: EventWriter<OutgoingMessage>,
+) {
+    for (entity, transform, battery) in &robots {
+        if battery.charge <= 0.0 {
+            info!("Robot {entity} disabled: battery empty");
+            known.release(entity);
+            statistics.robots_disabled += 1;
+            // Dernier appel à l'aide avant l'extinction de la radio
+            outgoing.send(OutgoingMessage {
+                sender: entity,
+                position: transform.translation.truncate(),
+                message: RobotMessage::HelpRequest,
+            });
+            commands
+                .entity(entity)
+                .insert(Disabled)
+                .remove::<(Target, Returning, Recharging, PathFollower)>();
+        }
+    }
+}
+
+// Un robot à la batterie faible abandonne sa tâche et rentre se recharger
+#[allow(clippy::type_complexity)]
+fn seek_charger(
+    mut commands: Commands,
+    robots: Query<(Entity, &Battery, Option<&Recharging>, Has<PathFollower>), Without<Disabled>>,
+    base: Option<Single<&Transform, With<Base>>>,
+    config: Res<SimulationConfig>,
+    mut known: KnownResources,
+) {
+    let Some(base) = base else {
+        return;
+    };
+    let base_position = base.translation.truncate();
+
+    for (entity, battery, recharging, following_path) in &robots {
+        match recharging {
+            None if battery.level() < config.battery.low_threshold => {
+                known.release(entity);
+                commands
+                    .entity(entity)
+                    .remove::<(Target, Returning)>()
+                    .insert((
+                        Recharging { docked: false },
+                        PathFollower::new(base_position),
+                    ));
+            }
+            // Chemin perdu ou inaccessible : nouvelle tentative
+            Some(Recharging { docked: false }) if !following_path => {
+                commands
+                    .entity(entity)
+                    .insert(PathFollower::new(base_position));
+            }
+            _ => {}
+        }
+    }
+}
+
+fn charge_at_base(
+    mut commands: Commands,
+    mut robots: Query<(Entity, &Transform, &mut Battery, &mut Recharging), Without<Disabled>>,
+    base: Option<Single<(&Transform, &Base)>>,
+    config: Res<SimulationConfig>,
+    time: Res<Time>,
+) {
+    let Some((base_transform, base)) = base.map(|base| base.into_inner()) else {
+        return;
+    };
+    let base_position = base_transform.translation.truncate();
+
+    for (entity, transform, mut battery, mut recharging) in robots.iter_mut() {
+        // Un robot à quai, que d'autres ont pu pousser, continue de se recharger
+        if !recharging.docked
+            && transform.translation.truncate().distance(base_position) >= base.radius
+        {
+            continue;
+        }
+
+        if !recharging.docked {
+            recharging.docked = true;
+            commands.entity(entity).remove::<PathFollower>();
+        }
+
+        battery.recharge(config.battery.charge_rate * time.delta_secs());
+        if battery.charge >= battery.capacity {
+            commands.entity(entity).remove::<Recharging>();
+        }
+    }
+}
@@ -0,0 +1,65 @@
+use crate::energy::Disabled;
+use crate::map::Map;
+use crate::robot::{Robot, Sensor};
+use crate::spatial::SpatialIndexSet;
+use bevy::prelude::*;
+
+pub struct ExplorationPlugin;
+
+impl Plugin for ExplorationPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<CellsExplored>();
+        app.init_resource::<Coverage>();
+        app.add_systems(
+            FixedUpdate,
+            record_exploration
+                .in_set(ExplorationSet)
+                .after(SpatialIndexSet),
+        );
+    }
+}
+
+// La grille d'exploration de la carte est à jour après ce set
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ExplorationSet;
+
+// Pourcentage de la carte déjà couvert par les capteurs
+#[derive(Resource, Default)]
+pub struct Coverage(pub f32);
+
+// Cases vues pour la première fois pendant ce pas de temps
+#[derive(Event)]
+pub struct CellsExplored(pub Vec<UVec2>);
+
+// Marque les cases couvertes par le capteur de chaque robot en service.
+// La carte n'est modifiée que si une case est révélée, pour ne pas la signaler changée à chaque pas.
+#[allow(clippy::type_complexity)]
+fn record_exploration(
+    robots: Query<(&Transform, &Children), (With<Robot>, Without<Disabled>)>,
+    sensors: Query<&Sensor>,
+    mut map: Single<&mut Map>,
+    mut coverage: ResMut<Coverage>,
+    mut explored: EventWriter<CellsExplored>,
+) {
+    let unseen: Vec<UVec2> = robots
+        .iter()
+        .filter_map(|(transform, children)| {
+            let sensor = children.iter().find_map(|&child| sensors.get(child).ok())?;
+            Some(sensor.footprint(&map, transform.translation.truncate()))
+        })
+        .flatten()
+        .filter(|&cell| !map.is_explored(cell))
+        .collect();
+    if unseen.is_empty() {
+        return;
+    }
+
+    // Deux capteurs peuvent révéler la même case : elle n'est comptée qu'une fois
+    let newly_explored: Vec<UVec2> = unseen
+        .into_iter()
+        .filter(|&cell| map.mark_explored(cell))
+        .collect();
+
+    coverage.0 = map.coverage() * 100.0;
+    explored.send(CellsExplored(newly_explored));
+}
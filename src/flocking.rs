@@ -0,0 +1,133 @@
+use crate::robot::Robot;
+use crate::state::{BehaviourSet, RobotState};
+use crate::steering::{alignment, blend, cohesion, separation, Agent};
+use crate::strategy::{BehaviorRegistry, Decision, Observation, RandomWalk, RobotBehavior};
+use bevy::prelude::*;
+use rand::RngCore;
+
+pub const FLOCKING_STRATEGY: &str = "Flocking";
+
+pub struct FlockingPlugin;
+
+impl Plugin for FlockingPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<BehaviorRegistry>();
+        app.world_mut()
+            .resource_mut::<BehaviorRegistry>()
+            .register(FLOCKING_STRATEGY, || Flocking);
+        app.init_resource::<SwarmMetrics>();
+        app.add_systems(FixedUpdate, measure_swarm.after(BehaviourSet::Resolve));
+    }
+}
+
+// Essaim à la manière des boids : séparation, alignement et cohésion avec les voisins,
+// pondérés selon le rôle du robot. Un robot isolé erre au hasard
+pub struct Flocking;
+
+impl RobotBehavior for Flocking {
+    fn decide(&mut self, observation: &Observation, rng: &mut dyn RngCore) -> Decision {
+        if observation.neighbours.is_empty() {
+            return RandomWalk.decide(observation, rng);
+        }
+        if !matches!(
+            observation.state,
+            RobotState::Wandering
+                | RobotState::Exploring
+                | RobotState::Returning
+                | RobotState::Collecting
+        ) {
+            return Decision::default();
+        }
+
+        let flocking = observation.flocking;
+        let agent = Agent::new(observation.position, observation.robot);
+        let positions: Vec<Vec2> = observation
+            .neighbours
+            .iter()
+            .map(|neighbour| neighbour.position)
+            .collect();
+        let velocities: Vec<Vec2> = observation
+            .neighbours
+            .iter()
+            .map(|neighbour| neighbour.velocity)
+            .collect();
+
+        let force = blend(&[
+            (
+                flocking.separation,
+                separation(&agent, &positions, flocking.separation_range),
+            ),
+            (flocking.alignment, alignment(&agent, &velocities)),
+            (flocking.cohesion, cohesion(&agent, &positions)),
+        ]);
+
+        // Vitesse constante : seule la direction suit les règles, pour qu'un groupe ne s'arrête pas
+        Decision {
+            speed: Some(1.0),
+            ..agent.decide(force, observation.robot.max_turn_rate * observation.delta)
+        }
+    }
+
+    fn needs_neighbours(&self) -> bool {
+        true
+    }
+}
+
+// Mouvement collectif des robots qui se déplacent, mis à jour à chaque pas de temps
+#[derive(Resource, Default, Debug)]
+pub struct SwarmMetrics {
+    pub polarisation: f32, // De 0 (directions opposées) à 1 (tous dans la même direction)
+    pub cohesion: f32,     // Distance moyenne au centre du groupe, en unités du monde
+    pub mean_polarisation: f32, // Moyennes depuis le début de la simulation
+    pub mean_cohesion: f32,
+    samples: u32,
+}
+
+// Norme de la moyenne des vecteurs unitaires des directions, données en radians : sans unité,
+// de 0 (directions opposées) à 1 (toutes identiques)
+pub fn polarisation(directions: &[f32]) -> f32 {
+    if directions.is_empty() {
+        return 0.0;
+    }
+    let sum: Vec2 = directions
+        .iter()
+        .map(|direction| Vec2::from_angle(*direction))
+        .sum();
+    sum.length() / directions.len() as f32
+}
+
+// Distance moyenne des positions à leur centre
+pub fn mean_distance_to_center(positions: &[Vec2]) -> f32 {
+    if positions.is_empty() {
+        return 0.0;
+    }
+    let center = positions.iter().sum::<Vec2>() / positions.len() as f32;
+    positions
+        .iter()
+        .map(|position| position.distance(center))
+        .sum::<f32>()
+        / positions.len() as f32
+}
+
+fn measure_swarm(
+    robots: Query<(&Transform, &Robot, &RobotState)>,
+    mut metrics: ResMut<SwarmMetrics>,
+) {
+    let (directions, positions): (Vec<f32>, Vec<Vec2>) = robots
+        .iter()
+        .filter(|(.., state)| !matches!(state, RobotState::Charging | RobotState::Disabled))
+        .map(|(transform, robot, _)| (robot.direction, transform.translation.truncate()))
+        .unzip();
+    if directions.is_empty() {
+        return;
+    }
+
+    metrics.polarisation = polarisation(&directions);
+    metrics.cohesion = mean_distance_to_center(&positions);
+
+    // Moyennes incrémentales
+    metrics.samples += 1;
+    let samples = metrics.samples as f32;
+    metrics.mean_polarisation += (metrics.polarisation - metrics.mean_polarisation) / samples;
+    metrics.mean_cohesion += (metrics.cohesion - metrics.mean_cohesion) / samples;
+}
@@ -0,0 +1,278 @@
+use crate::behaviour_tree::BehaviourTree;
+use crate::config::{ExplorationStrategy, SimulationConfig};
+use crate::energy::{Disabled, EnergySet, Recharging};
+use crate::knowledge::Knowledge;
+use crate::map::Map;
+use crate::pathfinding::{PathEvent, PathFollower, PathOutcome, PathfindingSet};
+use crate::robot::Explorer;
+use bevy::{math::uvec2, prelude::*};
+use std::collections::VecDeque;
+
+// Secondes entre deux recherches de frontières pour les explorateurs libres
+const REPLAN_INTERVAL: f32 = 0.5;
+
+pub struct FrontierPlugin;
+
+impl Plugin for FrontierPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            FixedUpdate,
+            (release_frontiers, assign_frontiers)
+                .chain()
+                .after(EnergySet)
+                .before(PathfindingSet),
+        );
+    }
+}
+
+// Frontière vers laquelle se dirige un explorateur
+#[derive(Component)]
+pub struct FrontierTarget {
+    pub goal: Vec2,
+    pub cell: UVec2,
+}
+
+// Frontières pour lesquelles l'explorateur n'a trouvé aucun chemin
+#[derive(Component, Default)]
+pub struct UnreachableFrontiers(pub Vec<UVec2>);
+
+// Groupe de cases frontières voisines
+#[derive(Clone, Debug)]
+pub struct FrontierCluster {
+    pub cells: Vec<UVec2>,
+    pub target: UVec2, // Case du groupe la plus proche de son centre
+}
+
+// Une case frontière est explorée, praticable et touche une case inexplorée de la carte
+pub fn is_frontier(map: &Map, cell: UVec2, explored: &impl Fn(UVec2) -> bool) -> bool {
+    if !map.is_walkable(cell) || !explored(cell) {
+        return false;
+    }
+
+    [IVec2::X, IVec2::NEG_X, IVec2::Y, IVec2::NEG_Y]
+        .into_iter()
+        .map(|offset| cell.as_ivec2() + offset)
+        .filter(|neighbour| neighbour.x >= 0 && neighbour.y >= 0)
+        .map(|neighbour| neighbour.as_uvec2())
+        .any(|neighbour| map.index(neighbour).is_some() && !explored(neighbour))
+}
+
+// Regroupe les cases frontières en 8-connexité, en ignorant les groupes trop petits
+pub fn frontier_clusters(
+    map: &Map,
+    explored: &impl Fn(UVec2) -> bool,
+    min_size: usize,
+) -> Vec<FrontierCluster> {
+    let cell_count = (map.width * map.height) as usize;
+    let mut frontier = vec![false; cell_count];
+    for y in 0..map.height {
+        for x in 0..map.width {
+            frontier[(y * map.width + x) as usize] = is_frontier(map, uvec2(x, y), explored);
+        }
+    }
+
+    let mut visited = vec![false; cell_count];
+    let mut clusters = vec![];
+
+    for start in 0..cell_count {
+        if !frontier[start] || visited[start] {
+            continue;
+        }
+
+        let mut cells = vec![];
+        let mut queue = VecDeque::from([start]);
+        visited[start] = true;
+
+        while let Some(index) = queue.pop_front() {
+            let cell = uvec2(index as u32 % map.width, index as u32 / map.width);
+            cells.push(cell);
+
+            for dy in -1..=1 {
+                for dx in -1..=1 {
+                    let neighbour = cell.as_ivec2() + IVec2::new(dx, dy);
+                    if neighbour.x < 0 || neighbour.y < 0 {
+                        continue;
+                    }
+                    let Some(neighbour) = map.index(neighbour.as_uvec2()) else {
+                        continue;
+                    };
+                    if frontier[neighbour] && !visited[neighbour] {
+                        visited[neighbour] = true;
+                        queue.push_back(neighbour);
+                    }
+                }
+            }
+        }
+
+        if cells.len() < min_size {
+            continue;
+        }
+
+        let centroid = cells.iter().map(|cell| cell.as_vec2()).sum::<Vec2>() / cells.len() as f32;
+        let target = *cells
+            .iter()
+            .min_by(|a, b| {
+                a.as_vec2()
+                    .distance_squared(centroid)
+                    .total_cmp(&b.as_vec2().distance_squared(centroid))
+            })
+            .expect("a cluster has at least one cell");
+
+        clusters.push(FrontierCluster { cells, target });
+    }
+
+    clusters
+}
+
+// Grille d'exploration vue par un explorateur : sa propre connaissance, ou la carte
+pub fn explored_by<'a>(
+    map: &'a Map,
+    knowledge: Option<&'a Knowledge>,
+) -> impl Fn(UVec2) -> bool + 'a {
+    move |cell| match knowledge {
+        Some(knowledge) => knowledge.explored.contains(cell),
+        None => map.is_explored(cell),
+    }
+}
+
+// Abandonne les frontières atteintes, inaccessibles ou déjà explorées
+#[allow(clippy::type_complexity)]
+fn release_frontiers(
+    mut commands: Commands,
+    mut explorers: Query<(
+        Entity,
+        &FrontierTarget,
+        &mut UnreachableFrontiers,
+        Option<&Knowledge>,
+        Has<Recharging>,
+        Has<Disabled>,
+    )>,
+    mut path_events: EventReader<PathEvent>,
+    map: Single<&Map>,
+) {
+    for event in path_events.read() {
+        let Ok((entity, target, mut unreachable, ..)) = explorers.get_mut(event.robot) else {
+            continue;
+        };
+        if target.goal != event.goal {
+            continue;
+        }
+
+        if event.outcome == PathOutcome::Unreachable {
+            unreachable.0.push(target.cell);
+        }
+        commands.entity(entity).remove::<FrontierTarget>();
+    }
+
+    for (entity, target, _, knowledge, recharging, disabled) in &explorers {
+        // La recharge et la mise hors service remplacent le chemin de l'explorateur
+        if recharging || disabled {
+            commands.entity(entity).remove::<FrontierTarget>();
+            continue;
+        }
+
+        if !is_frontier(&map, target.cell, &explored_by(&map, knowledge)) {
+            commands
+                .entity(entity)
+                .remove::<(FrontierTarget, PathFollower)>();
+        }
+    }
+}
+
+// Chaque explorateur libre rejoint le groupe de frontières le plus proche qui n'est pas
+// déjà visé par un autre explorateur
+#[allow(clippy::type_complexity)]
+fn assign_frontiers(
+    mut commands: Commands,
+    explorers: Query<
+        (
+            Entity,
+            &Transform,
+            Option<&FrontierTarget>,
+            Option<&UnreachableFrontiers>,
+            Option<&Knowledge>,
+        ),
+        (
+            With<Explorer>,
+            Without<BehaviourTree>,
+            Without<Recharging>,
+            Without<Disabled>,
+        ),
+    >,
+    map: Single<&Map>,
+    config: Res<SimulationConfig>,
+    time: Res<Time>,
+    mut since_replan: Local<Option<f32>>,
+) {
+    let exploration = &config.exploration;
+    if exploration.strategy != ExplorationStrategy::Frontier {
+        return;
+    }
+
+    // Le calcul des frontières parcourt toute la carte : il n'est pas refait à chaque pas
+    let elapsed = since_replan.get_or_insert(REPLAN_INTERVAL);
+    *elapsed += time.delta_secs();
+    if *elapsed < REPLAN_INTERVAL || explorers.iter().all(|(_, _, target, ..)| target.is_some()) {
+        return;
+    }
+    *elapsed = 0.0;
+
+    let mut goals: Vec<Vec2> = explorers
+        .iter()
+        .filter_map(|(_, _, target, ..)| target.map(|target| target.goal))
+        .collect();
+    let mut shared_clusters: Option<Vec<FrontierCluster>> = None;
+
+    for (entity, transform, target, unreachable, knowledge) in &explorers {
+        if target.is_some() {
+            continue;
+        }
+        let position = transform.translation.truncate();
+
+        let own_clusters;
+        let clusters = match knowledge {
+            Some(knowledge) => {
+                own_clusters = frontier_clusters(
+                    &map,
+                    &explored_by(&map, Some(knowledge)),
+                    exploration.min_frontier_size,
+                );
+                &own_clusters
+            }
+            None => shared_clusters.get_or_insert_with(|| {
+                frontier_clusters(
+                    &map,
+                    &explored_by(&map, None),
+                    exploration.min_frontier_size,
+                )
+            }),
+        };
+
+        let closest = clusters
+            .iter()
+            .map(|cluster| (cluster.target, map.cell_center(cluster.target)))
+            .filter(|(cell, _)| unreachable.is_none_or(|unreachable| !unreachable.0.contains(cell)))
+            .filter(|(_, goal)| {
+                goals
+                    .iter()
+                    .all(|other| other.distance(*goal) >= exploration.spread)
+            })
+            .min_by(|(_, a), (_, b)| {
+                a.distance_squared(position)
+                    .total_cmp(&b.distance_squared(position))
+            });
+
+        // Sans frontière disponible, l'explorateur continue sa marche aléatoire
+        if let Some((cell, goal)) = closest {
+            goals.push(goal);
+            commands
+                .entity(entity)
+                .insert((FrontierTarget { goal, cell }, PathFollower::new(goal)));
+            if unreachable.is_none() {
+                commands
+                    .entity(entity)
+                    .insert(UnreachableFrontiers::default());
+            }
+        }
+    }
+}
@@ -0,0 +1,312 @@
+use crate::common::{Base, DiscoveredResources, GameResource, Statistics};
+use crate::comms::{IncomingMessage, MessagingSet, RobotMessage};
+use crate::config::{KnowledgeModel, SimulationConfig};
+use crate::energy::Disabled;
+use crate::map::{Map, MapSetupSet};
+use crate::robot::{Robot, Sensor};
+use bevy::{ecs::system::SystemParam, prelude::*};
+
+pub struct KnowledgePlugin;
+
+impl Plugin for KnowledgePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, setup_base_knowledge.after(MapSetupSet));
+        app.add_systems(
+            FixedUpdate,
+            (observe_surroundings, apply_messages, share_at_base)
+                .chain()
+                .after(MessagingSet),
+        );
+    }
+}
+
+// Ensemble de cases de la carte, stocké sous forme de bits
+#[derive(Clone, Default, PartialEq, Debug)]
+pub struct CellSet {
+    width: u32,
+    bits: Vec<u64>,
+}
+
+impl CellSet {
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            bits: vec![0; (width as usize * height as usize).div_ceil(64)],
+        }
+    }
+
+    fn position(&self, cell: UVec2) -> Option<(usize, u64)> {
+        if cell.x >= self.width {
+            return None;
+        }
+        let index = (cell.y * self.width + cell.x) as usize;
+        (index / 64 < self.bits.len()).then_some((index / 64, 1 << (index % 64)))
+    }
+
+    pub fn insert(&mut self, cell: UVec2) {
+        if let Some((word, mask)) = self.position(cell) {
+            self.bits[word] |= mask;
+        }
+    }
+
+    pub fn contains(&self, cell: UVec2) -> bool {
+        self.position(cell)
+            .is_some_and(|(word, mask)| self.bits[word] & mask != 0)
+    }
+
+    pub fn union_with(&mut self, other: &CellSet) {
+        for (bits, other_bits) in self.bits.iter_mut().zip(&other.bits) {
+            *bits |= other_bits;
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.bits
+            .iter()
+            .map(|bits| bits.count_ones() as usize)
+            .sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bits.iter().all(|&bits| bits == 0)
+    }
+}
+
+// Ce qu'un robot (ou la base) sait de la carte, en mode `KnowledgeModel::Local`
+#[derive(Component, Clone, PartialEq, Debug)]
+pub struct Knowledge {
+    pub owner: Option<Entity>, // Robot détenteur, `None` pour la base
+    pub resources: DiscoveredResources,
+    pub collected: Vec<Entity>, // Ressources connues comme disparues, pour ne pas les réapprendre
+    pub explored: CellSet,
+    pub obstacles: CellSet,
+}
+
+impl Knowledge {
+    pub fn new(owner: Option<Entity>, width: u32, height: u32) -> Self {
+        Self {
+            owner,
+            resources: DiscoveredResources(vec![]),
+            collected: vec![],
+            explored: CellSet::new(width, height),
+            obstacles: CellSet::new(width, height),
+        }
+    }
+
+    pub fn knows(&self, resource: Entity) -> bool {
+        self.resources.contains(resource)
+    }
+
+    // Ajoute une ressource inconnue, sauf si elle a déjà été collectée
+    pub fn learn(&mut self, resource: Entity, position: Vec2) -> bool {
+        if self.collected.contains(&resource) {
+            return false;
+        }
+        self.resources.insert(resource, position)
+    }
+
+    pub fn forget(&mut self, resource: Entity) {
+        self.resources.remove(resource);
+        if !self.collected.contains(&resource) {
+            self.collected.push(resource);
+        }
+    }
+
+    // Intègre la connaissance d'un autre robot ou de la base.
+    // Chacun fait foi pour ses propres réservations.
+    pub fn merge(&mut self, other: &Knowledge) {
+        for &resource in &other.collected {
+            self.forget(resource);
+        }
+        for discovery in &other.resources.0 {
+            self.learn(discovery.resource, discovery.position);
+        }
+
+        for discovery in self.resources.0.iter_mut() {
+            let theirs = other
+                .resources
+                .0
+                .iter()
+                .find(|theirs| theirs.resource == discovery.resource);
+            let their_claim = theirs.and_then(|theirs| theirs.claimed_by);
+
+            if let Some(theirs) = theirs {
+                for &robot in &theirs.unreachable_for {
+                    if !discovery.unreachable_for.contains(&robot) {
+                        discovery.unreachable_for.push(robot);
+                    }
+                }
+            }
+
+            if discovery.claimed_by.is_some()
+                && discovery.claimed_by == other.owner
+                && their_claim != other.owner
+            {
+                discovery.claimed_by = None;
+            }
+            if discovery.claimed_by.is_none() && their_claim != self.owner {
+                discovery.claimed_by = their_claim;
+            }
+        }
+
+        self.explored.union_with(&other.explored);
+        self.obstacles.union_with(&other.obstacles);
+    }
+}
+
+// Ressources découvertes vues par un robot : sa propre connaissance s'il en a une,
+// la liste partagée par tous sinon
+#[derive(SystemParam)]
+pub struct KnownResources<'w, 's> {
+    shared: ResMut<'w, DiscoveredResources>,
+    knowledge: Query<'w, 's, &'static mut Knowledge>,
+}
+
+impl KnownResources<'_, '_> {
+    // Connaissance propre du robot, absente en mode partagé
+    pub fn knowledge(&self, robot: Entity) -> Option<&Knowledge> {
+        self.knowledge.get(robot).ok()
+    }
+
+    pub fn of(&mut self, robot: Entity) -> &mut DiscoveredResources {
+        match self.knowledge.get_mut(robot) {
+            Ok(knowledge) => &mut knowledge.into_inner().resources,
+            Err(_) => &mut self.shared,
+        }
+    }
+
+    // Enregistre une découverte, retourne `true` si personne ne la connaissait encore.
+    // La liste partagée sert aussi de bilan global en mode local.
+    pub fn discover(&mut self, robot: Entity, resource: Entity, position: Vec2) -> bool {
+        if let Ok(mut knowledge) = self.knowledge.get_mut(robot) {
+            knowledge.learn(resource, position);
+        }
+        self.shared.insert(resource, position)
+    }
+
+    pub fn collected(&mut self, robot: Entity, resource: Entity) {
+        if let Ok(mut knowledge) = self.knowledge.get_mut(robot) {
+            knowledge.forget(resource);
+        }
+        self.shared.remove(resource);
+    }
+
+    pub fn release(&mut self, robot: Entity) {
+        self.of(robot).release(robot);
+    }
+}
+
+fn setup_base_knowledge(
+    mut commands: Commands,
+    base: Option<Single<Entity, With<Base>>>,
+    config: Res<SimulationConfig>,
+) {
+    if let (Some(base), KnowledgeModel::Local) = (base, config.knowledge) {
+        commands
+            .entity(*base)
+            .insert(Knowledge::new(None, config.map.width, config.map.height));
+    }
+}
+
+// Chaque robot note les cases vues par son capteur et oublie les ressources disparues
+#[allow(clippy::type_complexity)]
+fn observe_surroundings(
+    mut robots: Query<(&Transform, &Children, &mut Knowledge), (With<Robot>, Without<Disabled>)>,
+    sensors: Query<&Sensor>,
+    resources: Query<(), With<GameResource>>,
+    map: Single<&Map>,
+) {
+    for (transform, children, mut knowledge) in robots.iter_mut() {
+        let Some(sensor) = children.iter().find_map(|&child| sensors.get(child).ok()) else {
+            continue;
+        };
+        let position = transform.translation.truncate();
+        let range = sensor.range as f32;
+
+        for cell in sensor.footprint(&map, position) {
+            knowledge.explored.insert(cell);
+            if !map.is_walkable(cell) {
+                knowledge.obstacles.insert(cell);
+            }
+        }
+
+        let missing: Vec<Entity> = knowledge
+            .resources
+            .0
+            .iter()
+            .filter(|discovery| discovery.position.distance(position) < range)
+            .map(|discovery| discovery.resource)
+            .filter(|&resource| !resources.contains(resource))
+            .collect();
+        for known in missing {
+            knowledge.forget(known);
+        }
+    }
+}
+
+// Les messages reçus complètent la connaissance du destinataire.
+// Une annonce de position signale un robot à portée : la connaissance qu'elle transporte,
+// celle de l'émetteur au moment de l'envoi, est fusionnée.
+fn apply_messages(
+    mut incoming: EventReader<IncomingMessage>,
+    mut knowledge: Query<&mut Knowledge>,
+    mut statistics: ResMut<Statistics>,
+) {
+    for received in incoming.read() {
+        let Ok(mut receiver) = knowledge.get_mut(received.receiver) else {
+            continue;
+        };
+
+        match &received.message {
+            RobotMessage::Discovery {
+                resource, position, ..
+            } => {
+                receiver.learn(*resource, *position);
+            }
+            RobotMessage::Claim { resource } => {
+                if let Some(discovery) = receiver.resources.get_mut(*resource) {
+                    discovery.claimed_by.get_or_insert(received.sender);
+                }
+            }
+            // Un robot hors service ne viendra plus chercher ce qu'il avait réservé
+            RobotMessage::HelpRequest => receiver.resources.release(received.sender),
+            RobotMessage::Position(Some(sender_knowledge)) => {
+                receiver.merge(sender_knowledge);
+                statistics.knowledge_merges += 1;
+            }
+            RobotMessage::Position(None) => {}
+        }
+    }
+}
+
+// La base sert de point d'échange : un robot qui y arrive dépose et reprend
+// tout ce qu'il sait
+#[allow(clippy::type_complexity)]
+fn share_at_base(
+    mut robots: Query<(Entity, &Transform, &mut Knowledge), (With<Robot>, Without<Base>)>,
+    base: Option<Single<(&Transform, &Base, &mut Knowledge), Without<Robot>>>,
+    mut at_base: Local<Vec<Entity>>,
+    mut statistics: ResMut<Statistics>,
+) {
+    let Some((base_transform, base, mut base_knowledge)) = base.map(|base| base.into_inner())
+    else {
+        return;
+    };
+    let base_position = base_transform.translation.truncate();
+
+    let mut arrived = vec![];
+    for (entity, transform, mut knowledge) in robots.iter_mut() {
+        if transform.translation.truncate().distance(base_position) >= base.radius {
+            continue;
+        }
+
+        arrived.push(entity);
+        if at_base.contains(&entity) {
+            continue;
+        }
+        base_knowledge.merge(&knowledge);
+        knowledge.merge(&base_knowledge);
+        statistics.knowledge_merges += 1;
+    }
+    *at_base = arrived;
+}
@@ -1,3 +1,48 @@
+pub mod auction;
+pub mod behaviour_tree;
+pub mod cli;
+pub mod collision;
 pub mod common;
+pub mod comms;
+pub mod config;
+pub mod display;
+pub mod energy;
+pub mod exploration;
+pub mod flocking;
+pub mod frontier;
+pub mod headless;
+pub mod knowledge;
 pub mod map;
+pub mod pathfinding;
 pub mod robot;
+pub mod spatial;
+pub mod state;
+pub mod steering;
+pub mod strategy;
+pub mod ui;
+
+use bevy::app::{PluginGroup, PluginGroupBuilder};
+
+// Plugins de la simulation, sans rendu : à compléter par `HeadlessPlugin` ou par une fenêtre
+// et `DisplayPlugin`
+pub struct SimulationPlugins;
+
+impl PluginGroup for SimulationPlugins {
+    fn build(self) -> PluginGroupBuilder {
+        PluginGroupBuilder::start::<Self>()
+            .add(common::CommonPlugin)
+            .add(map::MapPlugin)
+            .add(robot::RobotPlugin)
+            .add(spatial::SpatialPlugin)
+            .add(pathfinding::PathfindingPlugin)
+            .add(energy::EnergyPlugin)
+            .add(comms::CommsPlugin)
+            .add(knowledge::KnowledgePlugin)
+            .add(exploration::ExplorationPlugin)
+            .add(frontier::FrontierPlugin)
+            .add(auction::AuctionPlugin)
+            .add(behaviour_tree::BehaviourTreePlugin)
+            .add(flocking::FlockingPlugin)
+            .add(collision::CollisionPlugin)
+    }
+}
@@ -1,24 +1,54 @@
 use bevy::prelude::*;
 use bevy_simple_tilemap::plugin::SimpleTileMapPlugin;
-use common::CommonPlugin;
-use map::MapPlugin;
-use robot::RobotPlugin;
-use ui::UiPlugin;
-
-mod common;
-mod map;
-mod robot;
-mod ui;
-
-fn main() {
-    App::new()
-        .add_plugins((
-            DefaultPlugins,
-            SimpleTileMapPlugin,
-            CommonPlugin,
-            MapPlugin,
-            RobotPlugin,
-            UiPlugin,
-        ))
-        .run();
+use essaim_robots::cli::{Cli, USAGE};
+use essaim_robots::config::SimulationConfig;
+use essaim_robots::display::DisplayPlugin;
+use essaim_robots::headless::HeadlessPlugin;
+use essaim_robots::strategy::BehaviorRegistry;
+use essaim_robots::ui::UiPlugin;
+use essaim_robots::SimulationPlugins;
+
+fn main() -> AppExit {
+    let cli = match Cli::parse(std::env::args().skip(1)) {
+        Ok(cli) => cli,
+        Err(error) => {
+            eprintln!("{error}\n\n{USAGE}");
+            std::process::exit(2);
+        }
+    };
+
+    if cli.help {
+        println!("{USAGE}");
+        return AppExit::Success;
+    }
+
+    let config = match cli.config() {
+        Ok(config) => config,
+        Err(error) => {
+            eprintln!("{error}");
+            std::process::exit(1);
+        }
+    };
+
+    let mut app = App::new();
+
+    if cli.headless {
+        app.add_plugins(HeadlessPlugin);
+    } else {
+        app.add_plugins((DefaultPlugins, SimpleTileMapPlugin, DisplayPlugin, UiPlugin));
+    }
+
+    app.insert_resource(config).add_plugins(SimulationPlugins);
+
+    // Les stratégies désignées par la configuration sont enregistrées par les plugins
+    let world = app.world();
+    if let Err(error) = world
+        .resource::<BehaviorRegistry>()
+        .check(world.resource::<SimulationConfig>())
+    {
+        eprintln!("{error}");
+        std::process::exit(1);
+    }
+
+    app.run()
 }
@@ -1,28 +1,89 @@
-use crate::common::{Collider, GameResource, Obstacle, ResourceType};
+use crate::common::{Base, Collider, GameResource, ResourceType, SimulationRng};
+use crate::config::SimulationConfig;
 use bevy::math::{
     bounding::{Aabb2d, BoundingCircle, IntersectsVolume},
-    ivec3, vec2,
+    uvec2, vec2,
 };
 use bevy::prelude::*;
-use bevy_simple_tilemap::{Tile, TileMap};
 use noise::{NoiseFn, Perlin};
 use rand::Rng;
+use serde::Deserialize;
 use std::vec;
 
 pub struct MapPlugin;
 
 impl Plugin for MapPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Startup, (setup, draw_map, spawn_resources).chain());
+        app.add_systems(
+            Startup,
+            (setup, spawn_base, spawn_resources)
+                .chain()
+                .in_set(MapSetupSet),
+        );
     }
 }
 
+// Génération de la carte, de la base et des ressources au démarrage
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MapSetupSet;
+
+#[derive(Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum TerrainKind {
+    Plain,
+    Rock,
+    Sand,
+    Dune,
+    Highland,
+}
+
+impl TerrainKind {
+    pub fn is_walkable(&self) -> bool {
+        *self != TerrainKind::Rock
+    }
+
+    // Coût relatif de la traversée d'une case, utilisé par la planification de chemins
+    pub fn cost(&self) -> f32 {
+        match self {
+            TerrainKind::Plain => 1.0,
+            TerrainKind::Rock => f32::INFINITY,
+            TerrainKind::Sand => 1.5,
+            TerrainKind::Dune => 2.0,
+            TerrainKind::Highland => 3.0,
+        }
+    }
+
+    // Index de la tuile correspondante dans tile.png
+    pub fn sprite_index(&self) -> u32 {
+        match self {
+            TerrainKind::Plain => 0,
+            TerrainKind::Rock => 1,
+            TerrainKind::Sand => 2,
+            TerrainKind::Dune => 3,
+            TerrainKind::Highland => 4,
+        }
+    }
+}
+
+// Une cellule dont le bruit dépasse `above` prend le type `kind`.
+// Le seuil le plus élevé dépassé l'emporte, en dessous de tous les seuils la cellule est une plaine.
+#[derive(Deserialize, Clone, Copy, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct TerrainThreshold {
+    pub kind: TerrainKind,
+    pub above: f64,
+}
+
 #[derive(Component)]
 pub struct Map {
     pub width: u32,  // Number of tiles in the x-axis
     pub height: u32, // Number of tiles in the y-axis
     pub tile_size: u32,
     pub noise_map: Vec<f64>,
+    pub terrain: Vec<TerrainKind>,
+    pub obstacles: Vec<bool>, // Grille d'occupation, une case par tuile
+    pub revision: u32,        // Incrémentée à chaque modification de la grille d'occupation
+    pub explored: Vec<bool>,  // Cases déjà couvertes par le capteur d'un robot
+    pub explored_count: u32,
 }
 
 impl Map {
@@ -32,6 +93,11 @@ impl Map {
             height,
             tile_size,
             noise_map: vec![0.0; width as usize * height as usize],
+            terrain: vec![TerrainKind::Plain; width as usize * height as usize],
+            obstacles: vec![false; width as usize * height as usize],
+            revision: 0,
+            explored: vec![false; width as usize * height as usize],
+            explored_count: 0,
         }
     }
 
@@ -58,162 +124,405 @@ impl Map {
 
         map
     }
-}
 
-fn setup(
-    mut commands: Commands,
-    asset_server: Res<AssetServer>,
-    mut texture_atlas_layouts: ResMut<Assets<TextureAtlasLayout>>,
-    mut window: Single<&mut Window>,
-) {
-    const MAP_WIDTH: u32 = 250;
-    const MAP_HEIGHT: u32 = 250;
-    const MAP_SCALE: f64 = 15.0;
-    const SEED: u32 = 0;
-    const TILE_SIZE: u32 = 16;
-    const WINDOW_WIDTH: f32 = 1000.0;
-    const WINDOW_HEIGHT: f32 = 800.0;
-
-    let map = Map::from_perlin_noise(MAP_WIDTH, MAP_HEIGHT, TILE_SIZE, SEED, MAP_SCALE);
-    let texture_handle = asset_server.load::<Image>("tile.png");
-    let atlas_layout = TextureAtlasLayout::from_grid(UVec2::splat(TILE_SIZE), 10, 10, None, None);
-    let atlas_layout_handle = texture_atlas_layouts.add(atlas_layout);
-
-    window.resolution.set(WINDOW_WIDTH, WINDOW_HEIGHT);
-    window.title = String::from("Robots Exploration");
-    window.resizable = false;
+    pub fn classify(&mut self, thresholds: &[TerrainThreshold]) {
+        for (noise_value, terrain) in self.noise_map.iter().zip(self.terrain.iter_mut()) {
+            *terrain = thresholds
+                .iter()
+                .filter(|threshold| *noise_value > threshold.above)
+                .max_by(|a, b| a.above.total_cmp(&b.above))
+                .map_or(TerrainKind::Plain, |threshold| threshold.kind);
+        }
 
-    commands.spawn((
-        Camera2d,
-        Transform::from_xyz(WINDOW_WIDTH / 2.0, WINDOW_HEIGHT / 2.0, 0.0),
-    ));
-    commands.spawn((map, TileMap::new(texture_handle, atlas_layout_handle)));
-}
+        for (terrain, obstacle) in self.terrain.iter().zip(self.obstacles.iter_mut()) {
+            *obstacle = !terrain.is_walkable();
+        }
+        self.revision += 1;
+    }
+
+    pub fn index(&self, cell: UVec2) -> Option<usize> {
+        (cell.x < self.width && cell.y < self.height)
+            .then(|| (cell.y * self.width + cell.x) as usize)
+    }
+
+    // Les tuiles sont centrées sur (x * tile_size, y * tile_size)
+    pub fn cell_at(&self, world_pos: Vec2) -> Option<UVec2> {
+        let cell = (world_pos / self.tile_size as f32 + 0.5).floor();
+        if cell.x < 0.0 || cell.y < 0.0 {
+            return None;
+        }
+        let cell = uvec2(cell.x as u32, cell.y as u32);
+        self.index(cell).map(|_| cell)
+    }
+
+    pub fn cell_center(&self, cell: UVec2) -> Vec2 {
+        cell.as_vec2() * self.tile_size as f32
+    }
+
+    pub fn terrain(&self, cell: UVec2) -> Option<TerrainKind> {
+        self.index(cell).map(|index| self.terrain[index])
+    }
+
+    pub fn terrain_at(&self, world_pos: Vec2) -> Option<TerrainKind> {
+        self.cell_at(world_pos).and_then(|cell| self.terrain(cell))
+    }
+
+    // Hors de la carte, une cellule n'est jamais praticable
+    pub fn is_walkable(&self, cell: UVec2) -> bool {
+        self.index(cell).is_some_and(|index| !self.obstacles[index])
+    }
+
+    // Case praticable la plus proche (distance de Tchebychev croissante)
+    pub fn nearest_walkable(&self, cell: UVec2) -> Option<UVec2> {
+        let max_ring = self.width.max(self.height) as i32;
+
+        (0..=max_ring).find_map(|ring| {
+            (-ring..=ring)
+                .flat_map(|dy| (-ring..=ring).map(move |dx| IVec2::new(dx, dy)))
+                .filter(|offset| offset.x.abs() == ring || offset.y.abs() == ring)
+                .map(|offset| cell.as_ivec2() + offset)
+                .filter(|candidate| candidate.x >= 0 && candidate.y >= 0)
+                .map(|candidate| candidate.as_uvec2())
+                .find(|candidate| self.is_walkable(*candidate))
+        })
+    }
+
+    pub fn set_obstacle(&mut self, cell: UVec2, obstacle: bool) {
+        if let Some(index) = self.index(cell) {
+            if self.obstacles[index] != obstacle {
+                self.obstacles[index] = obstacle;
+                self.revision += 1;
+            }
+        }
+    }
+
+    pub fn cell_bounds(&self, cell: UVec2) -> Aabb2d {
+        Aabb2d::new(
+            self.cell_center(cell),
+            Vec2::splat(self.tile_size as f32 / 2.0),
+        )
+    }
+
+    // Cases de la carte recouvertes par un rectangle, bornées aux limites de la carte
+    fn cells_overlapping(&self, min: Vec2, max: Vec2) -> impl Iterator<Item = UVec2> {
+        let tile_size = self.tile_size as f32;
+        let first = (min / tile_size + 0.5).floor().max(Vec2::ZERO).as_ivec2();
+        let last = (max / tile_size + 0.5)
+            .floor()
+            .min(vec2(self.width as f32 - 1.0, self.height as f32 - 1.0))
+            .as_ivec2();
+
+        (first.y..=last.y)
+            .flat_map(move |y| (first.x..=last.x).map(move |x| uvec2(x as u32, y as u32)))
+    }
+
+    pub fn is_explored(&self, cell: UVec2) -> bool {
+        self.index(cell).is_some_and(|index| self.explored[index])
+    }
+
+    // Retourne `true` si la case n'avait encore jamais été vue
+    pub fn mark_explored(&mut self, cell: UVec2) -> bool {
+        match self.index(cell) {
+            Some(index) if !self.explored[index] => {
+                self.explored[index] = true;
+                self.explored_count += 1;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    // Fraction des cases explorées, entre 0 et 1
+    pub fn coverage(&self) -> f32 {
+        self.explored_count as f32 / self.explored.len() as f32
+    }
+
+    // Cases dont le centre est dans le disque, par exemple le champ d'un capteur
+    pub fn cells_within(&self, center: Vec2, radius: f32) -> impl Iterator<Item = UVec2> + '_ {
+        self.cells_overlapping(center - radius, center + radius)
+            .filter(move |&cell| self.cell_center(cell).distance_squared(center) <= radius * radius)
+    }
+
+    // Les limites de la carte ne comptent pas comme obstacles : seules les cases bloquées
+    pub fn circle_hits_obstacle(&self, center: Vec2, radius: f32) -> bool {
+        let circle = BoundingCircle::new(center, radius);
+
+        self.cells_overlapping(center - radius, center + radius)
+            .any(|cell| !self.is_walkable(cell) && circle.intersects(&self.cell_bounds(cell)))
+    }
+
+    // Déplacement d'un cercle de `start` à `end` : fraction du trajet (0 à 1) au premier
+    // contact avec un obstacle, ou `None` si le trajet est libre. Le centre parcourt un segment
+    // contre chaque case bloquée élargie du rayon (somme de Minkowski) : le calcul est exact et
+    // ne dépend pas de la taille du rayon
+    pub fn sweep_circle(&self, start: Vec2, end: Vec2, radius: f32) -> Option<f32> {
+        let motion = end - start;
+
+        self.cells_along(start, end, radius)
+            .filter(|&cell| !self.is_walkable(cell))
+            .filter_map(|cell| {
+                let bounds = self.cell_bounds(cell);
+                // Case élargie : deux rectangles et un disque à chaque coin
+                let wide = vec2(radius, 0.0);
+                let tall = vec2(0.0, radius);
+                [
+                    sweep_point_box(start, motion, bounds.min - wide, bounds.max + wide),
+                    sweep_point_box(start, motion, bounds.min - tall, bounds.max + tall),
+                ]
+                .into_iter()
+                .chain(
+                    [
+                        bounds.min,
+                        bounds.max,
+                        vec2(bounds.min.x, bounds.max.y),
+                        vec2(bounds.max.x, bounds.min.y),
+                    ]
+                    .map(|corner| sweep_point_circle(start, motion, corner, radius)),
+                )
+                .flatten()
+                .min_by(f32::total_cmp)
+            })
+            .min_by(f32::total_cmp)
+    }
 
-fn draw_map(map_query: Single<(&Map, &mut TileMap)>, mut commands: Commands) {
-    let (map, mut tile_map) = map_query.into_inner();
-
-    for y in 0..map.height {
-        for x in 0..map.width {
-            let noise_value = map.noise_map[(y * map.width + x) as usize];
-
-            let sprite_index = if noise_value > 0.75 {
-                4
-            } else if noise_value > 0.65 {
-                3
-            } else if noise_value > 0.35 {
-                2
-            } else if noise_value > 0.2 {
-                1
+    // Cases que peut toucher un cercle allant de `start` à `end`, rangée par rangée : sur
+    // chacune, seulement l'intervalle en x parcouru par le centre quand il est à moins d'un
+    // rayon de la rangée
+    fn cells_along(&self, start: Vec2, end: Vec2, radius: f32) -> impl Iterator<Item = UVec2> + '_ {
+        let tile_size = self.tile_size as f32;
+        let motion = end - start;
+        let first = ((start.y.min(end.y) - radius) / tile_size + 0.5)
+            .floor()
+            .max(0.0);
+        let last = ((start.y.max(end.y) + radius) / tile_size + 0.5)
+            .floor()
+            .min(self.height as f32 - 1.0);
+
+        (first as i32..=last as i32).flat_map(move |row| {
+            let low = (row as f32 - 0.5) * tile_size - radius;
+            let high = (row as f32 + 0.5) * tile_size + radius;
+            let span = if motion.y == 0.0 {
+                (low..=high).contains(&start.y).then_some((0.0, 1.0))
             } else {
-                0
+                let (a, b) = ((low - start.y) / motion.y, (high - start.y) / motion.y);
+                let (enter, exit) = (a.min(b).max(0.0), a.max(b).min(1.0));
+                (enter <= exit).then_some((enter, exit))
             };
 
-            if noise_value > 0.2 && noise_value < 0.35 {
-                commands.spawn((
-                    Collider {
-                        bounding_box: Aabb2d::new(
-                            vec2(
-                                x as f32 * map.tile_size as f32,
-                                y as f32 * map.tile_size as f32,
-                            ),
-                            vec2(map.tile_size as f32 / 2.0, map.tile_size as f32 / 2.0),
-                        ),
-                    },
-                    Obstacle,
-                    Transform::from_xyz(
-                        map.tile_size as f32 * x as f32,
-                        map.tile_size as f32 * y as f32,
-                        0.0,
-                    ),
-                ));
+            span.into_iter().flat_map(move |(enter, exit)| {
+                let (a, b) = (start.x + motion.x * enter, start.x + motion.x * exit);
+                let y = row as f32 * tile_size;
+                self.cells_overlapping(vec2(a.min(b) - radius, y), vec2(a.max(b) + radius, y))
+            })
+        })
+    }
+
+    // Le centre est sur une case bloquée ; hors de la carte, il ne l'est pas
+    pub fn is_blocked_at(&self, world_pos: Vec2) -> bool {
+        self.cell_at(world_pos)
+            .is_some_and(|cell| !self.is_walkable(cell))
+    }
+
+    // Taille de la zone où se déplacent les robots, depuis l'origine
+    pub fn extent(&self) -> Vec2 {
+        vec2(self.width as f32, self.height as f32) * self.tile_size as f32
+    }
+
+    // Le cercle dépasse de la carte ou touche une case bloquée. Un rayon nul ne teste que
+    // le centre
+    fn circle_blocked(&self, center: Vec2, radius: f32) -> bool {
+        let inside =
+            center.cmpge(Vec2::splat(radius)).all() && center.cmple(self.extent() - radius).all();
+        !inside
+            || if radius > 0.0 {
+                self.circle_hits_obstacle(center, radius)
+            } else {
+                self.is_blocked_at(center)
             }
+    }
+
+    // Déplacement d'un cercle de `motion` depuis `start` : il avance jusqu'au contact d'un
+    // obstacle ou d'un bord de la carte, puis glisse le long du mur avec le reste du
+    // déplacement, axe par axe. Le contact est calculé exactement par `sweep_circle`, donc le
+    // centre n'entre jamais dans une case bloquée ni ne sort de la carte, quels que soient la
+    // vitesse et le rayon. Un cercle qui touche déjà un obstacle n'est contraint que par son
+    // centre, pour qu'il puisse s'en dégager
+    pub fn slide_circle(&self, start: Vec2, motion: Vec2, radius: f32) -> Vec2 {
+        let radius = if radius > 0.0 && !self.circle_blocked(start, radius) {
+            radius
+        } else if !self.circle_blocked(start, 0.0) {
+            0.0
+        } else {
+            // Déjà dans un obstacle, par exemple apparu sous le robot : seuls les bords comptent
+            return (start + motion).clamp(Vec2::ZERO, self.extent());
+        };
 
-            tile_map.set_tile(
-                ivec3(x as i32, y as i32, 0),
-                Some(Tile {
-                    sprite_index,
-                    color: Color::WHITE,
-                    ..Default::default()
-                }),
-            );
+        let reached = self.advance_circle(start, motion, radius);
+        let rest = start + motion - reached;
+        if rest == Vec2::ZERO {
+            return reached;
+        }
+
+        // L'axe où il reste le plus à parcourir d'abord
+        let (first, second) = if rest.x.abs() >= rest.y.abs() {
+            (vec2(rest.x, 0.0), vec2(0.0, rest.y))
+        } else {
+            (vec2(0.0, rest.y), vec2(rest.x, 0.0))
+        };
+        let reached = self.advance_circle(reached, first, radius);
+        self.advance_circle(reached, second, radius)
+    }
+
+    // Avance jusqu'au premier contact avec un obstacle ou un bord de la carte, en s'arrêtant
+    // juste avant pour que le cercle reste libre
+    fn advance_circle(&self, start: Vec2, motion: Vec2, radius: f32) -> Vec2 {
+        // Distance gardée avec l'obstacle, en pixels
+        const SKIN: f32 = 0.01;
+
+        let length = motion.length();
+        if length == 0.0 {
+            return start;
+        }
+        let edge = sweep_out_of_box(start, motion, Vec2::splat(radius), self.extent() - radius);
+        let contact = self
+            .sweep_circle(start, start + motion, radius)
+            .into_iter()
+            .chain(edge)
+            .min_by(f32::total_cmp);
+        match contact {
+            Some(contact) => start + motion * (contact - SKIN / length).max(0.0),
+            None => start + motion,
         }
     }
 }
 
+// Premier instant (0 à 1) où un point allant de `start` à `start + motion` atteint le bord du
+// rectangle qui le contient
+fn sweep_out_of_box(start: Vec2, motion: Vec2, min: Vec2, max: Vec2) -> Option<f32> {
+    (0..2)
+        .filter(|&axis| motion[axis] != 0.0)
+        .map(|axis| {
+            let limit = if motion[axis] > 0.0 {
+                max[axis]
+            } else {
+                min[axis]
+            };
+            ((limit - start[axis]) / motion[axis]).max(0.0)
+        })
+        .filter(|&t| t <= 1.0)
+        .min_by(f32::total_cmp)
+}
+
+// Premier instant (0 à 1) où un point allant de `start` à `start + motion` est dans le
+// rectangle, par la méthode des tranches
+fn sweep_point_box(start: Vec2, motion: Vec2, min: Vec2, max: Vec2) -> Option<f32> {
+    let (mut enter, mut exit) = (0.0_f32, 1.0_f32);
+    for axis in 0..2 {
+        if motion[axis] == 0.0 {
+            if start[axis] < min[axis] || start[axis] > max[axis] {
+                return None;
+            }
+            continue;
+        }
+        let (a, b) = (
+            (min[axis] - start[axis]) / motion[axis],
+            (max[axis] - start[axis]) / motion[axis],
+        );
+        enter = enter.max(a.min(b));
+        exit = exit.min(a.max(b));
+        if enter > exit {
+            return None;
+        }
+    }
+    Some(enter)
+}
+
+// Premier instant (0 à 1) où un point allant de `start` à `start + motion` est dans le disque
+fn sweep_point_circle(start: Vec2, motion: Vec2, center: Vec2, radius: f32) -> Option<f32> {
+    let offset = start - center;
+    let c = offset.length_squared() - radius * radius;
+    if c <= 0.0 {
+        return Some(0.0);
+    }
+    let a = motion.length_squared();
+    let b = offset.dot(motion);
+    let discriminant = b * b - a * c;
+    // Immobile, s'éloignant du disque ou le manquant
+    if a == 0.0 || b >= 0.0 || discriminant < 0.0 {
+        return None;
+    }
+    let t = (-b - discriminant.sqrt()) / a;
+    (t <= 1.0).then_some(t)
+}
+
+fn setup(mut commands: Commands, config: Res<SimulationConfig>) {
+    let mut map = Map::from_perlin_noise(
+        config.map.width,
+        config.map.height,
+        config.map.tile_size,
+        config.seed,
+        config.map.scale,
+    );
+    map.classify(&config.map.terrain);
+
+    commands.spawn(map);
+}
+
+fn spawn_base(mut commands: Commands, map: Single<&Map>, config: Res<SimulationConfig>) {
+    let requested = uvec2(config.base.cell.0, config.base.cell.1);
+    let Some(cell) = map.nearest_walkable(requested) else {
+        warn!("No walkable cell for the base");
+        return;
+    };
+    let position = map.cell_center(cell);
+
+    commands.spawn((
+        Base {
+            radius: config.base.radius,
+        },
+        Transform::from_xyz(position.x, position.y, 0.25),
+    ));
+}
+
 fn spawn_resources(
     mut commands: Commands,
     map: Single<&Map>,
-    collider_query: Query<&Transform, With<Collider>>,
-    mut meshes: ResMut<Assets<Mesh>>,
-    mut materials: ResMut<Assets<ColorMaterial>>,
+    mut rng: ResMut<SimulationRng>,
+    config: Res<SimulationConfig>,
 ) {
-    const RESOURCE_COUNT: usize = 300;
-    const RESOURCE_SIZE: f32 = 8.0;
-    let mut rng = rand::thread_rng();
+    let resource_count = config.resources.count;
+    let resource_size = config.resources.size;
+    let rng = &mut rng.0;
 
     let map_width = map.width as f32 * map.tile_size as f32;
     let map_height = map.height as f32 * map.tile_size as f32;
 
-    // Collecter toutes les positions des obstacles
-    let obstacle_positions: Vec<Vec2> = collider_query
-        .iter()
-        .map(|transform| Vec2::new(transform.translation.x, transform.translation.y))
-        .collect();
-
     let mut resources_placed = 0;
     let mut attempts = 0;
 
-    while resources_placed < RESOURCE_COUNT && attempts < 1000 {
+    while resources_placed < resource_count && attempts < 1000 {
         attempts += 1;
 
         // Générer une position aléatoire
-        let x = rng.gen_range(RESOURCE_SIZE..map_width - RESOURCE_SIZE);
-        let y = rng.gen_range(RESOURCE_SIZE..map_height - RESOURCE_SIZE);
+        let x = rng.gen_range(resource_size..map_width - resource_size);
+        let y = rng.gen_range(resource_size..map_height - resource_size);
 
         // Vérifier s'il y a collision avec un obstacle
-        let resource_circle = BoundingCircle::new(Vec2::new(x, y), RESOURCE_SIZE);
-        let mut collision = false;
-
-        for obstacle_pos in &obstacle_positions {
-            let obstacle_box = Aabb2d::new(
-                *obstacle_pos,
-                Vec2::new(map.tile_size as f32 / 2.0, map.tile_size as f32 / 2.0),
-            );
-
-            if resource_circle.intersects(&obstacle_box) {
-                collision = true;
-                break;
-            }
-        }
+        let collision = map.circle_hits_obstacle(vec2(x, y), resource_size);
 
         // Si pas de collision, placer la ressource
         if !collision {
-            let (game_resource, color, mesh) = match rng.gen_range(0..3) {
-                0 => (
-                    GameResource::new(ResourceType::Energy),
-                    Color::srgb(1.0, 1.0, 0.0),
-                    Mesh2d(meshes.add(Circle::new(RESOURCE_SIZE))),
-                ),
-                1 => (
-                    GameResource::new(ResourceType::Mineral),
-                    Color::srgb(0.5, 0.5, 0.5),
-                    Mesh2d(meshes.add(Rectangle::new(RESOURCE_SIZE * 2.0, RESOURCE_SIZE * 2.0))),
-                ),
-                _ => (
-                    GameResource::new(ResourceType::Scientific),
-                    Color::srgb(0.0, 0.0, 1.0),
-                    Mesh2d(meshes.add(RegularPolygon::new(RESOURCE_SIZE, 3))),
-                ),
+            let game_resource = match rng.gen_range(0..3) {
+                0 => GameResource::new(ResourceType::Energy),
+                1 => GameResource::new(ResourceType::Mineral),
+                _ => GameResource::new(ResourceType::Scientific),
             };
             commands.spawn((
                 game_resource,
                 Collider {
-                    bounding_box: Aabb2d::new(vec2(x, y), vec2(RESOURCE_SIZE, RESOURCE_SIZE)),
+                    bounding_box: Aabb2d::new(vec2(x, y), vec2(resource_size, resource_size)),
                 },
                 Transform::from_xyz(x, y, 0.5),
-                mesh,
-                MeshMaterial2d(materials.add(color)),
             ));
 
             resources_placed += 1;
@@ -222,6 +531,6 @@ fn spawn_resources(
 
     info!(
         "Ressources placées: {}/{}",
-        resources_placed, RESOURCE_COUNT
+        resources_placed, resource_count
     );
 }
@@ -0,0 +1,348 @@
+use crate::config::SimulationConfig;
+use crate::map::Map;
+use crate::robot::Robot;
+use crate::steering::turn_towards;
+use bevy::{math::uvec2, prelude::*, utils::HashMap};
+use std::{cmp::Ordering, collections::BinaryHeap, collections::VecDeque};
+
+// Au-delà, le cache est vidé pour borner sa taille
+const PATH_CACHE_CAPACITY: usize = 1024;
+
+pub struct PathfindingPlugin;
+
+impl Plugin for PathfindingPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<PathEvent>();
+        app.init_resource::<PathCache>();
+        app.add_systems(
+            FixedUpdate,
+            (plan_paths, follow_path).chain().in_set(PathfindingSet),
+        );
+    }
+}
+
+// Les robots sont orientés le long de leur chemin avant de se déplacer
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PathfindingSet;
+
+// Fait suivre au robot un chemin planifié jusqu'à `goal`.
+// Le composant est retiré à l'arrivée ou si la cible est inaccessible.
+#[derive(Component)]
+pub struct PathFollower {
+    pub goal: Vec2,
+    pub waypoints: VecDeque<Vec2>,
+    pub revision: Option<u32>, // Révision de la carte lors de la planification, `None` pour replanifier
+}
+
+impl PathFollower {
+    pub fn new(goal: Vec2) -> Self {
+        Self {
+            goal,
+            waypoints: VecDeque::new(),
+            revision: None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PathOutcome {
+    Reached,
+    Unreachable,
+}
+
+#[derive(Event)]
+pub struct PathEvent {
+    pub robot: Entity,
+    pub goal: Vec2,
+    pub outcome: PathOutcome,
+}
+
+// Chemins déjà calculés, valables pour une révision de la carte
+#[derive(Resource, Default)]
+pub struct PathCache {
+    revision: u32,
+    paths: HashMap<(UVec2, UVec2, u32, bool), Option<Vec<Vec2>>>,
+}
+
+impl PathCache {
+    pub fn find_path(
+        &mut self,
+        map: &Map,
+        start: Vec2,
+        goal: Vec2,
+        clearance: f32,
+        terrain_cost: bool,
+    ) -> Option<Vec<Vec2>> {
+        if self.revision != map.revision || self.paths.len() >= PATH_CACHE_CAPACITY {
+            self.paths.clear();
+            self.revision = map.revision;
+        }
+
+        let (Some(start_cell), Some(goal_cell)) = (map.cell_at(start), map.cell_at(goal)) else {
+            return None;
+        };
+
+        // Le chemin en cache relie les centres des cases : les extrémités exactes sont recollées
+        let cells = self
+            .paths
+            .entry((start_cell, goal_cell, clearance.to_bits(), terrain_cost))
+            .or_insert_with(|| find_cell_path(map, start_cell, goal_cell, clearance, terrain_cost))
+            .clone()?;
+
+        Some(smooth_path(map, start, goal, &cells, clearance))
+    }
+}
+
+#[derive(PartialEq)]
+struct OpenNode {
+    cost: f32,
+    index: usize,
+}
+
+impl Eq for OpenNode {}
+
+impl Ord for OpenNode {
+    // Tas min sur le coût estimé, à égalité l'index le plus petit
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .cost
+            .total_cmp(&self.cost)
+            .then_with(|| other.index.cmp(&self.index))
+    }
+}
+
+impl PartialOrd for OpenNode {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+// A* sur la grille de la carte, en 8-connexité sans couper les coins.
+// `clearance` est le rayon du robot : une case n'est praticable que si un cercle
+// de ce rayon centré sur elle ne touche aucun obstacle. Les cases de départ et
+// d'arrivée n'ont besoin que d'être libres.
+// Retourne les centres des cases traversées, départ et arrivée inclus.
+pub fn find_cell_path(
+    map: &Map,
+    start: UVec2,
+    goal: UVec2,
+    clearance: f32,
+    terrain_cost: bool,
+) -> Option<Vec<Vec2>> {
+    let (start_index, goal_index) = (map.index(start)?, map.index(goal)?);
+    if !map.is_walkable(goal) {
+        return None;
+    }
+
+    let cell_count = (map.width * map.height) as usize;
+    let mut passable: Vec<Option<bool>> = vec![None; cell_count];
+    let mut is_passable = |cell: UVec2, index: usize| {
+        *passable[index].get_or_insert_with(|| {
+            index == start_index
+                || index == goal_index
+                || (map.is_walkable(cell)
+                    && !map.circle_hits_obstacle(map.cell_center(cell), clearance))
+        })
+    };
+
+    let cell_of = |index: usize| uvec2(index as u32 % map.width, index as u32 / map.width);
+    let heuristic = |cell: UVec2| {
+        let d = (cell.as_vec2() - goal.as_vec2()).abs();
+        d.max_element() + (std::f32::consts::SQRT_2 - 1.0) * d.min_element()
+    };
+
+    let mut best_cost = vec![f32::INFINITY; cell_count];
+    let mut came_from = vec![usize::MAX; cell_count];
+    let mut open = BinaryHeap::new();
+
+    best_cost[start_index] = 0.0;
+    open.push(OpenNode {
+        cost: heuristic(start),
+        index: start_index,
+    });
+
+    while let Some(OpenNode { cost, index }) = open.pop() {
+        let cell = cell_of(index);
+        if index == goal_index {
+            let mut path = vec![map.cell_center(cell)];
+            let mut current = index;
+            while current != start_index {
+                current = came_from[current];
+                path.push(map.cell_center(cell_of(current)));
+            }
+            path.reverse();
+            return Some(path);
+        }
+        if cost > best_cost[index] + heuristic(cell) {
+            continue;
+        }
+
+        for (dx, dy) in [
+            (1, 0),
+            (-1, 0),
+            (0, 1),
+            (0, -1),
+            (1, 1),
+            (1, -1),
+            (-1, 1),
+            (-1, -1),
+        ] {
+            let neighbour = cell.as_ivec2() + IVec2::new(dx, dy);
+            if neighbour.x < 0 || neighbour.y < 0 {
+                continue;
+            }
+            let neighbour = neighbour.as_uvec2();
+            let Some(neighbour_index) = map.index(neighbour) else {
+                continue;
+            };
+            if !is_passable(neighbour, neighbour_index) {
+                continue;
+            }
+
+            let diagonal = dx != 0 && dy != 0;
+            if diagonal {
+                let side_x = uvec2(neighbour.x, cell.y);
+                let side_y = uvec2(cell.x, neighbour.y);
+                let side_x_index = (side_x.y * map.width + side_x.x) as usize;
+                let side_y_index = (side_y.y * map.width + side_y.x) as usize;
+                if !is_passable(side_x, side_x_index) || !is_passable(side_y, side_y_index) {
+                    continue;
+                }
+            }
+
+            let step = if diagonal {
+                std::f32::consts::SQRT_2
+            } else {
+                1.0
+            };
+            let weight = match map.terrain(neighbour) {
+                Some(terrain) if terrain_cost && terrain.is_walkable() => terrain.cost(),
+                _ => 1.0,
+            };
+
+            let tentative = best_cost[index] + step * weight;
+            if tentative < best_cost[neighbour_index] {
+                best_cost[neighbour_index] = tentative;
+                came_from[neighbour_index] = index;
+                open.push(OpenNode {
+                    cost: tentative + heuristic(neighbour),
+                    index: neighbour_index,
+                });
+            }
+        }
+    }
+
+    None
+}
+
+// Remplace les extrémités par les positions exactes puis retire les points
+// intermédiaires inutiles lorsque la ligne droite est dégagée.
+// Le premier point (la position de départ) n'est pas inclus dans le résultat.
+pub fn smooth_path(
+    map: &Map,
+    start: Vec2,
+    goal: Vec2,
+    cells: &[Vec2],
+    clearance: f32,
+) -> Vec<Vec2> {
+    let mut points = vec![start];
+    points.extend(cells.iter().skip(1).take(cells.len().saturating_sub(2)));
+    points.push(goal);
+
+    let mut waypoints = Vec::new();
+    let mut anchor = 0;
+    while anchor < points.len() - 1 {
+        let mut next = anchor + 1;
+        for candidate in (anchor + 2..points.len()).rev() {
+            if map
+                .sweep_circle(points[anchor], points[candidate], clearance)
+                .is_none()
+            {
+                next = candidate;
+                break;
+            }
+        }
+        waypoints.push(points[next]);
+        anchor = next;
+    }
+
+    waypoints
+}
+
+fn plan_paths(
+    mut commands: Commands,
+    mut followers: Query<(Entity, &Transform, &Robot, &mut PathFollower)>,
+    map: Single<&Map>,
+    mut cache: ResMut<PathCache>,
+    config: Res<SimulationConfig>,
+    mut events: EventWriter<PathEvent>,
+) {
+    for (entity, transform, robot, mut follower) in followers.iter_mut() {
+        if follower.revision == Some(map.revision) {
+            continue;
+        }
+
+        let start = transform.translation.truncate();
+        match cache.find_path(
+            &map,
+            start,
+            follower.goal,
+            robot.radius,
+            config.pathfinding.terrain_cost,
+        ) {
+            Some(waypoints) => {
+                follower.waypoints = waypoints.into();
+                follower.revision = Some(map.revision);
+            }
+            None => {
+                events.send(PathEvent {
+                    robot: entity,
+                    goal: follower.goal,
+                    outcome: PathOutcome::Unreachable,
+                });
+                commands.entity(entity).remove::<PathFollower>();
+            }
+        }
+    }
+}
+
+fn follow_path(
+    mut commands: Commands,
+    mut followers: Query<(Entity, &Transform, &mut Robot, &mut PathFollower)>,
+    map: Single<&Map>,
+    time: Res<Time>,
+    mut events: EventWriter<PathEvent>,
+) {
+    for (entity, transform, mut robot, mut follower) in followers.iter_mut() {
+        if follower.revision.is_none() {
+            continue;
+        }
+
+        let position = transform.translation.truncate();
+        let tolerance = robot.radius.max(map.tile_size as f32 / 2.0);
+
+        while follower
+            .waypoints
+            .front()
+            .is_some_and(|waypoint| waypoint.distance(position) < tolerance)
+        {
+            follower.waypoints.pop_front();
+        }
+
+        let Some(waypoint) = follower.waypoints.front() else {
+            events.send(PathEvent {
+                robot: entity,
+                goal: follower.goal,
+                outcome: PathOutcome::Reached,
+            });
+            commands.entity(entity).remove::<PathFollower>();
+            continue;
+        };
+
+        // Rotation progressive vers le prochain point de passage
+        let target_direction = (waypoint.y - position.y).atan2(waypoint.x - position.x);
+        let max_rotation = robot.max_turn_rate * time.delta_secs();
+
+        robot.direction = turn_towards(robot.direction, target_direction, max_rotation);
+    }
+}
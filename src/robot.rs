@@ -1,34 +1,75 @@
 use crate::{
-    common::{Collider, DiscoveredResources, GameResource, Obstacle, ResourceCollectedEvent},
-    map::Map,
+    behaviour_tree::BehaviourTree,
+    collision::spawn_spots,
+    common::{
+        Base, Collider, GameResource, ResourceCollectedEvent, ResourceType, SimulationRng,
+        Statistics,
+    },
+    comms::{MessagingSet, OutgoingMessage, RobotMessage},
+    config::{AllocationStrategy, KnowledgeModel, RobotConfig, SimulationConfig},
+    energy::{Battery, Disabled, EnergySet, Recharging},
+    knowledge::{Knowledge, KnownResources},
+    map::{Map, MapSetupSet},
+    pathfinding::{PathEvent, PathFollower, PathOutcome, PathfindingSet},
+    spatial::{ResourceIndex, SpatialIndexSet},
+    state::{BehaviourSet, RobotState, StatePlugin},
+    strategy::StrategyPlugin,
 };
 use bevy::{
-    math::{
-        bounding::{BoundingCircle, BoundingVolume, IntersectsVolume},
-        vec2,
-    },
+    math::bounding::{BoundingCircle, IntersectsVolume},
     prelude::*,
 };
-use core::f32;
 use rand::Rng;
 
 pub struct RobotPlugin;
 
 impl Plugin for RobotPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Startup, spawn_robots);
+        // L'état des robots ordonne leur déplacement et l'interrompt à quai ou hors service
+        if !app.is_plugin_added::<StatePlugin>() {
+            app.add_plugins(StatePlugin);
+        }
+        // Les stratégies intégrées guident les robots qui n'ont pas de chemin à suivre
+        if !app.is_plugin_added::<StrategyPlugin>() {
+            app.add_plugins(StrategyPlugin);
+        }
+        app.add_systems(Startup, spawn_robots.after(MapSetupSet));
+        app.configure_sets(
+            FixedUpdate,
+            AllocationSet.after(EnergySet).before(PathfindingSet),
+        );
         app.add_systems(
-            Update,
-            (seek, check_collisions, collect_resource, sense_resource),
+            FixedUpdate,
+            (
+                (
+                    (unload_cargo, release_targets)
+                        .chain()
+                        .before(AllocationSet),
+                    assign_targets.after(AllocationSet),
+                )
+                    .after(EnergySet)
+                    .before(PathfindingSet),
+                seek.in_set(BehaviourSet::Move),
+                (collect_resource, sense_resource)
+                    .chain()
+                    .after(SpatialIndexSet)
+                    .before(MessagingSet),
+            ),
         );
     }
 }
 
+// Attribution des ressources aux collecteurs libres, entre la libération des cibles
+// et le retour à la base des collecteurs sans cible
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AllocationSet;
+
 #[derive(Component)]
 pub struct Robot {
     pub direction: f32, // Direction en radians
     pub radius: f32,
-    pub speed: f32,
+    pub speed: f32,    // Vitesse maximale
+    pub throttle: f32, // Fraction de la vitesse maximale, choisie par la stratégie
     pub max_turn_rate: f32,
     pub max_deviation: f32,
 }
@@ -36,6 +77,30 @@ pub struct Robot {
 #[derive(Component)]
 pub struct Sensor {
     pub range: u32,
+    pub detecting: bool,       // Une ressource est dans le rayon de détection
+    pub nearest: Option<Vec2>, // Position de la plus proche, pour un collecteur celle qu'il peut ramasser
+}
+
+impl Sensor {
+    // Cases couvertes par le capteur d'un robot placé en `position`
+    pub fn footprint<'a>(&self, map: &'a Map, position: Vec2) -> impl Iterator<Item = UVec2> + 'a {
+        map.cells_within(position, self.range as f32)
+    }
+}
+
+// Nom du rôle d'un robot, qui désigne son arbre de comportement dans la configuration
+#[derive(Component, Clone, Debug, PartialEq, Eq)]
+pub struct Role(pub String);
+
+impl Role {
+    fn from_config(robot_config: &RobotConfig, default: &str) -> Self {
+        Self(
+            robot_config
+                .role
+                .clone()
+                .unwrap_or_else(|| default.to_string()),
+        )
+    }
 }
 
 #[derive(Component)]
@@ -44,134 +109,159 @@ pub struct Explorer;
 #[derive(Component)]
 pub struct Collector;
 
-#[derive(Resource)]
-pub struct SensorMaterial {
-    on: Handle<ColorMaterial>,
-    detected: Handle<ColorMaterial>,
+// Ressource découverte par un explorateur vers laquelle se dirige un collecteur
+#[derive(Component)]
+pub struct Target {
+    pub resource: Entity,
+    pub position: Vec2,
+}
+
+// Ressources ramassées par un collecteur, créditées une fois déchargées à la base
+#[derive(Component, Default)]
+pub struct Cargo {
+    pub capacity: u32,
+    pub items: Vec<GameResource>,
 }
 
+impl Cargo {
+    pub fn is_full(&self) -> bool {
+        self.items.len() as u32 >= self.capacity
+    }
+}
+
+// Collecteur en route vers la base pour décharger
+#[derive(Component)]
+pub struct Returning;
+
 fn spawn_robots(
     mut commands: Commands,
-    mut meshes: ResMut<Assets<Mesh>>,
-    mut materials: ResMut<Assets<ColorMaterial>>,
+    config: Res<SimulationConfig>,
+    base: Option<Single<&Transform, With<Base>>>,
+    map: Option<Single<&Map>>,
 ) {
-    let sensor_material_resource = SensorMaterial {
-        on: materials.add(Color::hsla(207.0, 1.9, 0.5, 0.2)),
-        detected: materials.add(Color::hsla(105.0, 0.55, 0.48, 0.2)),
-    };
-
-    // // Explorateurs
-    for _ in 0..2 {
-        commands
-            .spawn((
-                Mesh2d(meshes.add(Circle::new(5.0))),
-                Transform::from_xyz(0.0, 0.0, 1.0),
-                MeshMaterial2d(materials.add(Color::hsla(0.0, 0.0, 0.0, 1.0))),
-                Explorer,
-                Robot {
-                    direction: 0.0,
-                    radius: 5.0,
-                    speed: 70.0,
-                    max_turn_rate: 5.0,
-                    max_deviation: f32::consts::FRAC_PI_2,
-                },
-            ))
-            .with_child((
-                Sensor { range: 200 },
-                Mesh2d(meshes.add(Circle::new(200.0))),
-                MeshMaterial2d(sensor_material_resource.on.clone()),
-            ));
+    // Les robots partent de la base, côte à côte s'ils ne peuvent pas se chevaucher
+    let start = base.map_or(Vec2::ZERO, |base| base.translation.truncate());
+    let count = config.explorers.count + config.collectors.count;
+    let mut starts = match map {
+        Some(map) if config.collisions.robots => {
+            let radius = config.explorers.radius.max(config.collectors.radius);
+            spawn_spots(&map, start, radius, count)
+        }
+        _ => vec![start; count],
+    }
+    .into_iter();
+
+    // Explorateurs
+    for start in starts.by_ref().take(config.explorers.count) {
+        spawn_robot(
+            &mut commands,
+            (Explorer, Role::from_config(&config.explorers, "Explorer")),
+            &config,
+            &config.explorers,
+            start,
+        );
     }
 
     // Collecteurs de ressources
-    for _ in 0..2 {
-        commands
-            .spawn((
-                Mesh2d(meshes.add(Circle::new(10.0))),
-                Transform::from_xyz(0.0, 0.0, 1.0),
-                MeshMaterial2d(materials.add(Color::hsla(0.0, 0.0, 0.0, 1.0))),
+    for start in starts {
+        spawn_robot(
+            &mut commands,
+            (
                 Collector,
-                Robot {
-                    direction: 0.0,
-                    radius: 10.0,
-                    speed: 50.0,
-                    max_turn_rate: 10.0,
-                    max_deviation: f32::consts::FRAC_PI_2,
+                Role::from_config(&config.collectors, "Collector"),
+                Cargo {
+                    capacity: config.collectors.capacity,
+                    items: vec![],
                 },
-            ))
-            .with_child((
-                Sensor { range: 100 },
-                Mesh2d(meshes.add(Circle::new(100.0))),
-                MeshMaterial2d(sensor_material_resource.on.clone()),
-            ));
+            ),
+            &config,
+            &config.collectors,
+            start,
+        );
     }
-
-    commands.insert_resource(sensor_material_resource);
 }
 
-fn seek(mut query: Query<(&mut Transform, &mut Robot)>, time: Res<Time>, map: Single<&Map>) {
-    let mut rng = rand::thread_rng();
-    let map_width = map.width as f32 * map.tile_size as f32;
-    let map_height = map.height as f32 * map.tile_size as f32;
-
-    for (mut transform, mut robot) in query.iter_mut() {
-        transform.translation.x += robot.direction.cos() * robot.speed * time.delta_secs();
-        transform.translation.y += robot.direction.sin() * robot.speed * time.delta_secs();
-
-        // Modifier légèrement la direction actuelle
-        let turn_amount =
-            rng.gen_range(-robot.max_turn_rate..robot.max_turn_rate) * time.delta_secs();
-
-        robot.direction += turn_amount;
-
-        // Rebondir sur les bords
-        if transform.translation.x < robot.radius {
-            transform.translation.x = robot.radius;
-            robot.direction = std::f32::consts::PI - robot.direction;
-        } else if transform.translation.x > map_width - robot.radius {
-            transform.translation.x = map_width - robot.radius;
-            robot.direction = std::f32::consts::PI - robot.direction;
-        }
-        if transform.translation.y < robot.radius {
-            transform.translation.y = robot.radius;
-            robot.direction = -robot.direction;
-        } else if transform.translation.y > map_height - robot.radius {
-            transform.translation.y = map_height - robot.radius;
-            robot.direction = -robot.direction;
-        }
+fn spawn_robot(
+    commands: &mut Commands,
+    role: impl Bundle,
+    config: &SimulationConfig,
+    robot_config: &RobotConfig,
+    start: Vec2,
+) {
+    let mut robot = commands.spawn((
+        Transform::from_xyz(start.x, start.y, 1.0),
+        role,
+        Robot {
+            direction: 0.0,
+            radius: robot_config.radius,
+            speed: robot_config.speed,
+            throttle: 1.0,
+            max_turn_rate: robot_config.max_turn_rate,
+            max_deviation: robot_config.max_deviation,
+        },
+        RobotState::default(),
+    ));
+    robot.with_child((
+        Sensor {
+            range: robot_config.sensor_range,
+            detecting: false,
+            nearest: None,
+        },
+        Transform::default(),
+    ));
+
+    if config.battery.enabled {
+        robot.insert(Battery::new(config.battery.capacity));
+    }
+    if config.knowledge == KnowledgeModel::Local {
+        let owner = robot.id();
+        robot.insert(Knowledge::new(
+            Some(owner),
+            config.map.width,
+            config.map.height,
+        ));
     }
 }
 
-fn check_collisions(
-    mut robots_query: Query<(&mut Transform, &mut Robot)>,
-    obstacles_query: Query<&Collider, With<Obstacle>>,
+// Déplacement sans jamais entrer dans un obstacle ni sortir de la carte : le robot glisse le
+// long des murs sans changer de direction et rebondit sur les bords. Il ne se détourne au
+// hasard que s'il ne peut ni avancer ni glisser
+fn seek(
+    mut query: Query<(&mut Transform, &mut Robot, &RobotState)>,
     time: Res<Time>,
+    map: Single<&Map>,
+    mut rng: ResMut<SimulationRng>,
 ) {
-    let mut rng = rand::thread_rng();
-
-    for (transform, mut robot) in robots_query.iter_mut() {
-        // Position actuelle
-        let current_pos = vec2(transform.translation.x, transform.translation.y);
-
-        // Calculer le vecteur de déplacement basé sur la direction
-        let dx = robot.direction.cos() * robot.speed * time.delta_secs();
-        let dy = robot.direction.sin() * robot.speed * time.delta_secs();
+    // En dessous de cette fraction du déplacement voulu, le robot est considéré bloqué
+    const MIN_PROGRESS: f32 = 0.1;
 
-        // Nouvelle position prévue
-        let new_pos = vec2(current_pos.x + dx, current_pos.y + dy);
+    let rng = &mut rng.0;
+    let extent = map.extent();
 
-        // Vérifier les collisions à la nouvelle position
-        let robot_bounding_circle = BoundingCircle::new(new_pos, robot.radius);
-        let mut collision_detected = false;
+    for (mut transform, mut robot, state) in query.iter_mut() {
+        if !state.is_moving() {
+            continue;
+        }
 
-        for obstacle_collider in &obstacles_query {
-            if robot_bounding_circle.intersects(&obstacle_collider.bounding_box) {
-                collision_detected = true;
-                break;
-            }
+        let start = transform.translation.truncate();
+        let motion =
+            Vec2::from_angle(robot.direction) * robot.speed * robot.throttle * time.delta_secs();
+        let reached = map.slide_circle(start, motion, robot.radius);
+        transform.translation.x = reached.x;
+        transform.translation.y = reached.y;
+
+        // Rebondir sur les bords : la direction est réfléchie si le pas suivant en sortirait
+        let next = reached + motion;
+        let leaves_x = next.x < robot.radius || next.x > extent.x - robot.radius;
+        let leaves_y = next.y < robot.radius || next.y > extent.y - robot.radius;
+        if leaves_x {
+            robot.direction = std::f32::consts::PI - robot.direction;
+        }
+        if leaves_y {
+            robot.direction = -robot.direction;
         }
 
-        if collision_detected {
+        if !leaves_x && !leaves_y && (reached - start).length() < motion.length() * MIN_PROGRESS {
             robot.direction += rng.gen_range(-robot.max_deviation..robot.max_deviation)
                 + if rng.gen_bool(0.5) {
                     std::f32::consts::FRAC_PI_2
@@ -181,128 +271,316 @@ fn check_collisions(
         }
     }
 }
+
+#[allow(clippy::type_complexity)]
 fn collect_resource(
     mut commands: Commands,
-    mut resources_query: Query<(Entity, &Collider, &GameResource)>,
-    collectors: Query<(&Transform, &Robot), With<Collector>>,
-    mut events: EventWriter<ResourceCollectedEvent>,
-    mut discovered_resources: ResMut<DiscoveredResources>,
+    resources_query: Query<(&Collider, &GameResource)>,
+    mut collectors: Query<
+        (Entity, &Transform, &Robot, &mut Cargo, Option<&mut Battery>),
+        (With<Collector>, Without<Disabled>),
+    >,
+    resource_index: Res<ResourceIndex>,
+    config: Res<SimulationConfig>,
+    mut known: KnownResources,
 ) {
-    for (robot_transform, robot) in &collectors {
-        for (resource_entity, resource_collider, resource) in resources_query.iter_mut() {
-            let robot_bounding_circle = BoundingCircle::new(
-                vec2(robot_transform.translation.x, robot_transform.translation.y),
-                robot.radius,
-            );
+    // Les ressources ramassées ne disparaissent qu'à la fin du système : chacune est réservée
+    // par le premier collecteur qui la touche, les suivants l'ignorent
+    let mut taken: Vec<Entity> = vec![];
+
+    for (entity, robot_transform, robot, mut cargo, mut battery) in collectors.iter_mut() {
+        let robot_pos = robot_transform.translation.truncate();
+        let robot_bounding_circle = BoundingCircle::new(robot_pos, robot.radius);
+
+        // Une ressource touchée a son centre à moins du rayon du robot plus sa demi-diagonale
+        let reach = robot.radius + config.resources.size * std::f32::consts::SQRT_2;
+
+        for (resource_entity, _) in resource_index.within_radius(robot_pos, reach) {
+            let Ok((resource_collider, resource)) = resources_query.get(resource_entity) else {
+                continue;
+            };
+
+            if cargo.is_full() {
+                break;
+            }
+
+            // Une ressource réservée par un autre collecteur lui est laissée
+            let claimed_by_other = known.of(entity).0.iter().any(|discovery| {
+                discovery.resource == resource_entity
+                    && discovery
+                        .claimed_by
+                        .is_some_and(|claimant| claimant != entity)
+            });
+            if taken.contains(&resource_entity) || claimed_by_other {
+                continue;
+            }
 
             if robot_bounding_circle.intersects(&resource_collider.bounding_box) {
-                events.send(ResourceCollectedEvent {
-                    points: resource.points,
-                });
+                taken.push(resource_entity);
 
-                // Retirer la ressource de la liste des ressources découvertes si elle est présente
-                for idx in 0..discovered_resources.0.len() {
-                    let res = discovered_resources.0.get(idx).unwrap();
-                    if res.x == resource_collider.bounding_box.center().x
-                        && res.y == resource_collider.bounding_box.center().y
-                    {
-                        info!("Removed from discovered resources: {:?}", res);
-                        discovered_resources.0.remove(idx);
-                        break;
+                // Une ressource d'énergie peut servir à recharger le robot plutôt qu'être rapportée
+                match (config.battery.energy_pickup, battery.as_deref_mut()) {
+                    (Some(charge), Some(battery)) if resource.kind == ResourceType::Energy => {
+                        battery.recharge(charge);
                     }
+                    _ => cargo.items.push(*resource),
                 }
 
+                // Retirer la ressource des ressources découvertes si elle est présente
+                known.collected(entity, resource_entity);
+
                 commands.entity(resource_entity).despawn();
             }
         }
     }
 }
 
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
 fn sense_resource(
-    mut sensors_query: Query<(&mut Parent, &Sensor, &mut MeshMaterial2d<ColorMaterial>)>,
-    mut parent_query: Query<(
-        &mut Transform,
-        &mut Robot,
-        Option<&Collector>,
-        Option<&Explorer>,
-    )>,
-    resources_query: Query<&Transform, (With<GameResource>, Without<Robot>)>,
-    time: Res<Time>,
-    sensor_material: Res<SensorMaterial>,
-    mut discovered_resources: ResMut<DiscoveredResources>,
+    mut sensors_query: Query<(&Parent, &mut Sensor)>,
+    parent_query: Query<(&Transform, Has<Collector>, Has<Explorer>, Has<Disabled>)>,
+    resources_query: Query<&GameResource>,
+    resource_index: Res<ResourceIndex>,
+    mut known: KnownResources,
+    mut statistics: ResMut<Statistics>,
+    mut outgoing: EventWriter<OutgoingMessage>,
 ) {
-    const ROTATION_SPEED: f32 = 2.0;
+    for (parent, mut sensor) in sensors_query.iter_mut() {
+        let robot_entity = parent.get();
+        let Ok((robot_transform, collector, explorer, disabled)) = parent_query.get(robot_entity)
+        else {
+            continue;
+        };
+
+        // Le capteur d'un robot hors service est éteint
+        if disabled {
+            if sensor.detecting {
+                sensor.detecting = false;
+                sensor.nearest = None;
+            }
+            continue;
+        }
 
-    for (parent, sensor, mut material) in sensors_query.iter_mut() {
-        let parent_result = parent_query.get_mut(parent.get());
+        let robot_pos = robot_transform.translation.truncate();
+
+        // Un collecteur ignore les ressources réservées par un autre
+        let reserved: Vec<Entity> = if collector {
+            known
+                .of(robot_entity)
+                .0
+                .iter()
+                .filter(|discovery| {
+                    discovery
+                        .claimed_by
+                        .is_some_and(|claimant| claimant != robot_entity)
+                })
+                .map(|discovery| discovery.resource)
+                .collect()
+        } else {
+            vec![]
+        };
+
+        // Trouver la ressource la plus proche dans le rayon de détection
+        let closest_resource = resource_index
+            .nearest(robot_pos, sensor.range as f32, |entity| {
+                resources_query.contains(entity) && !reserved.contains(&entity)
+            })
+            .and_then(|(entity, resource_pos)| {
+                Some((entity, resource_pos, resources_query.get(entity).ok()?.kind))
+            });
+
+        if sensor.detecting != closest_resource.is_some() {
+            sensor.detecting = closest_resource.is_some();
+        }
+        let nearest = closest_resource.map(|(_, resource_pos, _)| resource_pos);
+        if sensor.nearest != nearest {
+            sensor.nearest = nearest;
+        }
 
-        if let Ok((robot_transform, mut robot, collector, explorer)) = parent_result {
-            let mut closest_resource: Option<(Transform, f32)> = None;
+        let Some((resource_entity, resource_pos, kind)) = closest_resource else {
+            continue;
+        };
+        if explorer {
+            // Vérifier si la ressource est déjà connue de l'explorateur
+            let already_discovered = known.of(robot_entity).contains(resource_entity);
+            if !already_discovered {
+                // Seules les découvertes inconnues de tous comptent dans le bilan
+                if known.discover(robot_entity, resource_entity, resource_pos) {
+                    statistics.discoveries += 1;
+                    info!("New dicovery!: {:?}", resource_pos);
+                }
+                outgoing.send(OutgoingMessage {
+                    sender: robot_entity,
+                    position: robot_pos,
+                    message: RobotMessage::Discovery {
+                        resource: resource_entity,
+                        position: resource_pos,
+                        kind,
+                    },
+                });
+            }
+        }
+    }
+}
 
-            // Trouver la ressource la plus proche dans le rayon de détection
-            for resource_transform in &resources_query {
-                let distance_to_resource = robot_transform
-                    .translation
-                    .distance(resource_transform.translation);
+// Chaque collecteur libre réserve la ressource découverte non réservée la plus proche
+// et planifie un chemin jusqu'à elle
+// Un collecteur plein, ou chargé sans ressource à aller chercher, rentre à la base
+#[allow(clippy::type_complexity)]
+fn assign_targets(
+    mut commands: Commands,
+    collectors: Query<
+        (Entity, &Transform, &Cargo),
+        (
+            With<Collector>,
+            Without<Target>,
+            Without<Returning>,
+            Without<Recharging>,
+            Without<Disabled>,
+            Without<BehaviourTree>,
+        ),
+    >,
+    base: Option<Single<&Transform, With<Base>>>,
+    mut known: KnownResources,
+    mut outgoing: EventWriter<OutgoingMessage>,
+    config: Res<SimulationConfig>,
+) {
+    // Aux enchères, les ressources sont attribuées dans AllocationSet
+    let claim_nearest = config.allocation.strategy == AllocationStrategy::Nearest;
+    let base_position = base.map(|base| base.translation.truncate());
+
+    for (entity, transform, cargo) in &collectors {
+        let position = transform.translation.truncate();
+        let return_to_base = |commands: &mut Commands| {
+            if let Some(base_position) = base_position {
+                commands
+                    .entity(entity)
+                    .insert((Returning, PathFollower::new(base_position)));
+            }
+        };
 
-                if distance_to_resource < sensor.range as f32 {
-                    if closest_resource.is_none()
-                        || distance_to_resource < closest_resource.unwrap().1
-                    {
-                        closest_resource = Some((*resource_transform, distance_to_resource));
-                    }
-                }
+        if cargo.is_full() {
+            return_to_base(&mut commands);
+            continue;
+        }
+
+        let closest = known
+            .of(entity)
+            .0
+            .iter_mut()
+            .filter(|discovery| {
+                claim_nearest
+                    && discovery.claimed_by.is_none()
+                    && !discovery.unreachable_for.contains(&entity)
+            })
+            .min_by(|a, b| {
+                a.position
+                    .distance_squared(position)
+                    .total_cmp(&b.position.distance_squared(position))
+            });
+
+        if let Some(discovery) = closest {
+            discovery.claimed_by = Some(entity);
+            outgoing.send(OutgoingMessage {
+                sender: entity,
+                position,
+                message: RobotMessage::Claim {
+                    resource: discovery.resource,
+                },
+            });
+            commands.entity(entity).insert((
+                Target {
+                    resource: discovery.resource,
+                    position: discovery.position,
+                },
+                PathFollower::new(discovery.position),
+            ));
+        } else if !cargo.items.is_empty() {
+            return_to_base(&mut commands);
+        }
+    }
+}
+
+// Libère les collecteurs dont la cible a été collectée, atteinte ou est inaccessible
+fn release_targets(
+    mut commands: Commands,
+    collectors: Query<(Entity, &Target, &Cargo), With<Collector>>,
+    mut path_events: EventReader<PathEvent>,
+    mut known: KnownResources,
+) {
+    for event in path_events.read() {
+        let Ok((entity, target, _)) = collectors.get(event.robot) else {
+            continue;
+        };
+        if target.position != event.goal {
+            continue;
+        }
+
+        if let Some(discovery) = known.of(entity).get_mut(target.resource) {
+            discovery.claimed_by = None;
+            // Une ressource inaccessible n'est plus proposée à ce collecteur
+            if event.outcome == PathOutcome::Unreachable {
+                info!("Unreachable discovery: {:?}", target.position);
+                discovery.unreachable_for.push(entity);
             }
+        }
 
-            if let Some((resource_transform, _)) = closest_resource {
-                *material = MeshMaterial2d(sensor_material.detected.clone());
+        commands.entity(entity).remove::<Target>();
+    }
 
-                if let Some(_) = collector {
-                    let dx = resource_transform.translation.x - robot_transform.translation.x;
-                    let dy = resource_transform.translation.y - robot_transform.translation.y;
+    for (entity, target, cargo) in &collectors {
+        let still_claimed = known
+            .of(entity)
+            .get_mut(target.resource)
+            .is_some_and(|discovery| discovery.claimed_by == Some(entity));
 
-                    // Calculer la direction vers la ressource (y, x) pour atan2
-                    let target_direction = dy.atan2(dx);
+        // Un collecteur rempli en chemin abandonne sa cible pour rentrer
+        if cargo.is_full() {
+            known.release(entity);
+        }
 
-                    // Rotation progressive vers la cible
-                    let mut angle_diff = target_direction - robot.direction;
+        if !still_claimed || cargo.is_full() {
+            commands.entity(entity).remove::<(Target, PathFollower)>();
+        }
+    }
+}
 
-                    // Normaliser la différence d'angle entre -PI et PI
-                    while angle_diff > std::f32::consts::PI {
-                        angle_diff -= 2.0 * std::f32::consts::PI;
-                    }
-                    while angle_diff < -std::f32::consts::PI {
-                        angle_diff += 2.0 * std::f32::consts::PI;
-                    }
+// Décharge la cargaison des collecteurs arrivés à la base et crédite les points
+fn unload_cargo(
+    mut commands: Commands,
+    mut collectors: Query<(Entity, &Transform, &mut Cargo, Has<Returning>), With<Collector>>,
+    base: Option<Single<(&Transform, &Base)>>,
+    mut path_events: EventReader<PathEvent>,
+    mut events: EventWriter<ResourceCollectedEvent>,
+) {
+    let Some((base_transform, base)) = base.map(|base| base.into_inner()) else {
+        return;
+    };
+    let base_position = base_transform.translation.truncate();
 
-                    // Appliquer la rotation avec une vitesse limitée
-                    let rotation_amount = angle_diff.signum()
-                        * (angle_diff.abs().min(ROTATION_SPEED * time.delta_secs()));
-
-                    robot.direction += rotation_amount;
-                } else if let Some(_) = explorer {
-                    // Vérifier si la ressource a déjà été découverte
-                    let mut already_discovered = false;
-                    for resource in &discovered_resources.0 {
-                        if resource.x == resource_transform.translation.x
-                            && resource.y == resource_transform.translation.y
-                        {
-                            already_discovered = true;
-                            break;
-                        }
-                    }
-                    if !already_discovered {
-                        discovered_resources.0.push(vec2(
-                            resource_transform.translation.x,
-                            resource_transform.translation.y,
-                        ));
+    for (entity, transform, mut cargo, returning) in collectors.iter_mut() {
+        let at_base = transform.translation.truncate().distance(base_position) < base.radius;
 
-                        info!("New dicovery!: {:?}", discovered_resources.0);
-                    }
-                }
-            } else {
-                *material = MeshMaterial2d(sensor_material.on.clone());
+        if at_base && !cargo.items.is_empty() {
+            for resource in cargo.items.drain(..) {
+                events.send(ResourceCollectedEvent {
+                    kind: resource.kind,
+                    points: resource.points,
+                });
             }
         }
+
+        if returning && cargo.items.is_empty() {
+            commands
+                .entity(entity)
+                .remove::<(Returning, PathFollower)>();
+        }
+    }
+
+    // Sans chemin vers la base, le collecteur reprend son exploration et réessaiera plus tard
+    for event in path_events.read() {
+        if event.outcome == PathOutcome::Unreachable && event.goal == base_position {
+            commands.entity(event.robot).remove::<Returning>();
+        }
     }
 }
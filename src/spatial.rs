@@ -0,0 +1,162 @@
+use crate::common::GameResource;
+use crate::robot::Robot;
+use bevy::{prelude::*, utils::HashMap};
+use std::marker::PhantomData;
+
+// Taille d'une case de la table de hachage spatiale, en unités du monde
+const CELL_SIZE: f32 = 64.0;
+
+pub struct SpatialPlugin;
+
+impl Plugin for SpatialPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(ResourceIndex::new(CELL_SIZE));
+        app.insert_resource(RobotIndex::new(CELL_SIZE));
+        app.add_systems(
+            FixedUpdate,
+            (index_moved::<GameResource>, index_moved::<Robot>).in_set(SpatialIndexSet),
+        );
+        // Les suppressions sont suivies à chaque frame pour ne manquer aucun despawn
+        app.add_systems(
+            PostUpdate,
+            (index_removed::<GameResource>, index_removed::<Robot>),
+        );
+    }
+}
+
+// Les systèmes qui interrogent les index s'exécutent après ce set
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SpatialIndexSet;
+
+pub type ResourceIndex = SpatialIndex<GameResource>;
+pub type RobotIndex = SpatialIndex<Robot>;
+
+// Table de hachage spatiale des entités portant le composant `T`.
+// Une entité despawnée peut rester indexée jusqu'à la fin de la frame :
+// les appelants doivent ignorer les entités qui n'existent plus.
+#[derive(Resource)]
+pub struct SpatialIndex<T> {
+    cell_size: f32,
+    cells: HashMap<IVec2, Vec<(Entity, Vec2)>>,
+    positions: HashMap<Entity, Vec2>,
+    marker: PhantomData<fn() -> T>,
+}
+
+impl<T> SpatialIndex<T> {
+    pub fn new(cell_size: f32) -> Self {
+        Self {
+            cell_size,
+            cells: HashMap::default(),
+            positions: HashMap::default(),
+            marker: PhantomData,
+        }
+    }
+
+    fn cell(&self, pos: Vec2) -> IVec2 {
+        (pos / self.cell_size).floor().as_ivec2()
+    }
+
+    pub fn len(&self) -> usize {
+        self.positions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.positions.is_empty()
+    }
+
+    pub fn position(&self, entity: Entity) -> Option<Vec2> {
+        self.positions.get(&entity).copied()
+    }
+
+    // Ajoute l'entité ou met à jour sa position
+    pub fn insert(&mut self, entity: Entity, pos: Vec2) {
+        let cell = self.cell(pos);
+
+        if let Some(previous) = self.positions.insert(entity, pos) {
+            let previous_cell = self.cell(previous);
+            if previous_cell == cell {
+                if let Some(entry) = self
+                    .cells
+                    .get_mut(&cell)
+                    .and_then(|entries| entries.iter_mut().find(|(e, _)| *e == entity))
+                {
+                    entry.1 = pos;
+                }
+                return;
+            }
+            self.remove_from_cell(previous_cell, entity);
+        }
+
+        self.cells.entry(cell).or_default().push((entity, pos));
+    }
+
+    pub fn remove(&mut self, entity: Entity) {
+        if let Some(pos) = self.positions.remove(&entity) {
+            self.remove_from_cell(self.cell(pos), entity);
+        }
+    }
+
+    fn remove_from_cell(&mut self, cell: IVec2, entity: Entity) {
+        if let Some(entries) = self.cells.get_mut(&cell) {
+            entries.retain(|(e, _)| *e != entity);
+            if entries.is_empty() {
+                self.cells.remove(&cell);
+            }
+        }
+    }
+
+    // Entités à une distance strictement inférieure à `radius` du centre
+    pub fn within_radius(
+        &self,
+        center: Vec2,
+        radius: f32,
+    ) -> impl Iterator<Item = (Entity, Vec2)> + '_ {
+        let first = self.cell(center - radius);
+        let last = self.cell(center + radius);
+
+        (first.y..=last.y)
+            .flat_map(move |y| (first.x..=last.x).map(move |x| IVec2::new(x, y)))
+            .filter_map(|cell| self.cells.get(&cell))
+            .flatten()
+            .filter(move |(_, pos)| pos.distance_squared(center) < radius * radius)
+            .copied()
+    }
+
+    // Entité la plus proche dans le rayon donné, à égalité la première rencontrée
+    pub fn nearest(
+        &self,
+        center: Vec2,
+        max_distance: f32,
+        mut filter: impl FnMut(Entity) -> bool,
+    ) -> Option<(Entity, Vec2)> {
+        let mut closest: Option<(Entity, Vec2, f32)> = None;
+
+        for (entity, pos) in self.within_radius(center, max_distance) {
+            let distance = pos.distance_squared(center);
+            if closest.is_none_or(|(_, _, best)| distance < best) && filter(entity) {
+                closest = Some((entity, pos, distance));
+            }
+        }
+
+        closest.map(|(entity, pos, _)| (entity, pos))
+    }
+}
+
+#[allow(clippy::type_complexity)]
+fn index_moved<T: Component>(
+    mut index: ResMut<SpatialIndex<T>>,
+    query: Query<(Entity, &Transform), (With<T>, Changed<Transform>)>,
+) {
+    for (entity, transform) in &query {
+        index.insert(entity, transform.translation.truncate());
+    }
+}
+
+fn index_removed<T: Component>(
+    mut index: ResMut<SpatialIndex<T>>,
+    mut removed: RemovedComponents<T>,
+) {
+    for entity in removed.read() {
+        index.remove(entity);
+    }
+}
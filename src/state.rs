@@ -0,0 +1,241 @@
+use crate::common::SimulationRng;
+use crate::energy::{Disabled, Recharging};
+use crate::frontier::FrontierTarget;
+use crate::pathfinding::{PathFollower, PathfindingSet};
+use crate::robot::{Cargo, Collector, Returning, Robot, Sensor, Target};
+use crate::spatial::SpatialIndexSet;
+use bevy::prelude::*;
+use rand::Rng;
+
+// Secondes sans avancer d'un rayon avant qu'un robot soit considéré bloqué
+const STUCK_TIME: f32 = 2.0;
+
+pub struct StatePlugin;
+
+impl Plugin for StatePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<StateChanged>();
+        app.configure_sets(
+            FixedUpdate,
+            (
+                BehaviourSet::Transition,
+                BehaviourSet::Steer,
+                BehaviourSet::Move,
+                BehaviourSet::Resolve,
+            )
+                .chain()
+                .after(PathfindingSet)
+                .before(SpatialIndexSet),
+        );
+        app.add_systems(
+            FixedUpdate,
+            (update_states, escape)
+                .chain()
+                .in_set(BehaviourSet::Transition),
+        );
+    }
+}
+
+// Étapes du comportement des robots, une fois les décisions prises et les chemins suivis :
+// changement d'état, orientation propre à chaque état, déplacement, puis correction des
+// chevauchements
+#[derive(SystemSet, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BehaviourSet {
+    Transition,
+    Steer,
+    Move,
+    Resolve,
+}
+
+// Ce que fait un robot, déduit à chaque pas de temps de ses composants
+#[derive(Component, Clone, Copy, PartialEq, Eq, Debug, Default)]
+#[require(Progress)]
+pub enum RobotState {
+    #[default]
+    Wandering, // Marche aléatoire, sans but
+    Exploring,   // Explorateur en route vers une frontière
+    Approaching, // Collecteur en route vers une ressource réservée ou détectée
+    Collecting,  // Collecteur chargé, à la recherche d'autres ressources
+    Returning,   // En route vers la base, pour décharger ou se recharger
+    Charging,    // À quai à la base
+    Stuck,       // N'avance plus malgré ses efforts
+    Disabled,    // Batterie vide, définitivement
+}
+
+impl RobotState {
+    pub fn can_become(self, next: RobotState) -> bool {
+        // Un robot hors service le reste
+        self != RobotState::Disabled || next == RobotState::Disabled
+    }
+
+    // Un robot à quai ou hors service ne bouge pas
+    pub fn is_moving(self) -> bool {
+        !matches!(self, RobotState::Charging | RobotState::Disabled)
+    }
+}
+
+// Envoyé à chaque changement d'état d'un robot
+#[derive(Event, Clone, Copy, Debug, PartialEq)]
+pub struct StateChanged {
+    pub robot: Entity,
+    pub from: RobotState,
+    pub to: RobotState,
+}
+
+// Ce qui détermine l'état d'un robot
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Situation {
+    pub disabled: bool,
+    pub docked: bool,
+    pub recharging: bool,
+    pub returning: bool,
+    pub stuck: bool,
+    pub target: bool,   // Collecteur avec une ressource réservée
+    pub sensing: bool,  // Collecteur qui détecte une ressource à ramasser
+    pub carrying: bool, // Collecteur dont la cargaison n'est pas vide
+    pub frontier: bool, // Explorateur avec une frontière à atteindre
+}
+
+// Table des transitions : la première condition remplie, par ordre de priorité, l'emporte
+pub fn next_state(situation: &Situation) -> RobotState {
+    if situation.disabled {
+        RobotState::Disabled
+    } else if situation.docked {
+        RobotState::Charging
+    } else if situation.stuck {
+        RobotState::Stuck
+    } else if situation.recharging || situation.returning {
+        RobotState::Returning
+    } else if situation.target || situation.sensing {
+        RobotState::Approaching
+    } else if situation.carrying {
+        RobotState::Collecting
+    } else if situation.frontier {
+        RobotState::Exploring
+    } else {
+        RobotState::Wandering
+    }
+}
+
+// Applique une transition autorisée et la signale, retourne `true` si l'état a changé
+pub fn change_state(
+    robot: Entity,
+    state: &mut Mut<RobotState>,
+    next: RobotState,
+    events: &mut EventWriter<StateChanged>,
+) -> bool {
+    let from = **state;
+    if from == next || !from.can_become(next) {
+        return false;
+    }
+
+    debug!("Robot {robot}: {from:?} -> {next:?}");
+    **state = next;
+    events.send(StateChanged {
+        robot,
+        from,
+        to: next,
+    });
+    true
+}
+
+// Progression d'un robot, pour détecter qu'il est bloqué
+#[derive(Component, Default)]
+pub struct Progress {
+    anchor: Option<Vec2>,
+    idle: f32, // Secondes passées à moins d'un rayon de `anchor`
+}
+
+#[allow(clippy::type_complexity)]
+fn update_states(
+    mut robots: Query<(
+        Entity,
+        &Transform,
+        &Robot,
+        &Children,
+        &mut RobotState,
+        &mut Progress,
+        Has<Disabled>,
+        Option<&Recharging>,
+        Has<Returning>,
+        Has<Target>,
+        Has<FrontierTarget>,
+        Has<Collector>,
+        Option<&Cargo>,
+    )>,
+    sensors: Query<&Sensor>,
+    time: Res<Time>,
+    mut events: EventWriter<StateChanged>,
+) {
+    for (
+        entity,
+        transform,
+        robot,
+        children,
+        mut state,
+        mut progress,
+        disabled,
+        recharging,
+        returning,
+        target,
+        frontier,
+        collector,
+        cargo,
+    ) in robots.iter_mut()
+    {
+        let docked = recharging.is_some_and(|recharging| recharging.docked);
+        let position = transform.translation.truncate();
+
+        // Un robot immobile par choix, à quai ou arrêté par sa stratégie, n'est pas bloqué
+        let stopped = robot.speed * robot.throttle == 0.0;
+        let anchor = progress.anchor.get_or_insert(position);
+        if disabled || docked || stopped || anchor.distance(position) >= robot.radius {
+            progress.anchor = Some(position);
+            progress.idle = 0.0;
+        } else {
+            progress.idle += time.delta_secs();
+        }
+
+        let sensing = collector
+            && children
+                .iter()
+                .filter_map(|&child| sensors.get(child).ok())
+                .any(|sensor| sensor.nearest.is_some());
+
+        let next = next_state(&Situation {
+            disabled,
+            docked,
+            recharging: recharging.is_some(),
+            returning,
+            stuck: progress.idle >= STUCK_TIME,
+            target,
+            sensing,
+            carrying: cargo.is_some_and(|cargo| !cargo.items.is_empty()),
+            frontier,
+        });
+        change_state(entity, &mut state, next, &mut events);
+    }
+}
+
+// Un robot qui vient de se bloquer repart dans une direction aléatoire et replanifie son chemin
+fn escape(
+    mut robots: Query<(
+        &mut Robot,
+        &RobotState,
+        &mut Progress,
+        Option<&mut PathFollower>,
+    )>,
+    mut rng: ResMut<SimulationRng>,
+) {
+    for (mut robot, state, mut progress, follower) in robots.iter_mut() {
+        if *state != RobotState::Stuck {
+            continue;
+        }
+
+        robot.direction = rng.0.gen_range(-std::f32::consts::PI..std::f32::consts::PI);
+        progress.idle = 0.0;
+        if let Some(mut follower) = follower {
+            follower.revision = None;
+        }
+    }
+}
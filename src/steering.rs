@@ -0,0 +1,292 @@
+use crate::map::Map;
+use crate::robot::Robot;
+use crate::strategy::{Decision, Observation};
+use bevy::prelude::*;
+use rand::{Rng, RngCore};
+use serde::Deserialize;
+
+// Comportements de pilotage : chacun retourne une force, écart entre la vitesse voulue et la
+// vitesse actuelle. Les forces sont pondérées et additionnées par `blend`, puis la somme est
+// convertie en direction et en vitesse par `Agent::decide`
+
+// État cinématique d'un robot
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Agent {
+    pub position: Vec2,
+    pub direction: f32, // En radians, conservée même à l'arrêt
+    pub velocity: Vec2,
+    pub max_speed: f32,
+}
+
+impl Agent {
+    pub fn new(position: Vec2, robot: &Robot) -> Self {
+        Self {
+            position,
+            direction: robot.direction,
+            velocity: Vec2::from_angle(robot.direction) * robot.speed * robot.throttle,
+            max_speed: robot.speed,
+        }
+    }
+
+    // Applique une force : la vitesse est bornée à `max_speed`, la rotation à `max_turn` radians
+    pub fn decide(&self, force: Vec2, max_turn: f32) -> Decision {
+        let velocity = (self.velocity + force).clamp_length_max(self.max_speed);
+        let heading = if velocity == Vec2::ZERO {
+            self.direction
+        } else {
+            // Angle relatif, pour que la direction reste continue
+            self.direction + Vec2::from_angle(self.direction).angle_to(velocity)
+        };
+
+        Decision {
+            heading: Some(turn_towards(self.direction, heading, max_turn)),
+            speed: Some(if self.max_speed > 0.0 {
+                velocity.length() / self.max_speed
+            } else {
+                0.0
+            }),
+            ..Default::default()
+        }
+    }
+
+    // Position d'une cible mobile au moment où l'agent pourrait l'atteindre
+    fn predict(&self, position: Vec2, velocity: Vec2) -> Vec2 {
+        if self.max_speed <= 0.0 {
+            return position;
+        }
+        position + velocity * (self.position.distance(position) / self.max_speed)
+    }
+}
+
+// Somme des forces pondérées
+pub fn blend(forces: &[(f32, Vec2)]) -> Vec2 {
+    forces.iter().map(|(weight, force)| force * weight).sum()
+}
+
+// Comportement de pilotage désigné par la configuration, évalué sur ce qu'observe le robot
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq)]
+pub enum Steering {
+    Wander,              // Errance, écart limité par la vitesse de rotation du robot
+    SeekResource,        // Vers la ressource que détecte le capteur
+    ArriveResource(f32), // Vers la ressource détectée, en ralentissant à moins de ce rayon
+    FleeNeighbour(f32),  // À l'opposé du voisin le plus proche, s'il est à moins de ce rayon
+    PursueNeighbour,     // Vers la position à venir du voisin le plus proche
+    EvadeNeighbour(f32), // Comme FleeNeighbour, d'après la position à venir du voisin
+    Separation(f32),     // À l'écart des voisins plus proches que ce rayon
+    Alignment,           // Dans la direction moyenne des voisins
+    Cohesion,            // Vers le centre des voisins
+    // Contourne les obstacles à moins de `look_ahead` devant le robot
+    ObstacleAvoidance { look_ahead: f32 },
+}
+
+// Comportements pondérés d'un robot, combinés par `blend`
+pub type WeightedSteering = [(f32, Steering)];
+
+// Distance à laquelle les stratégies intégrées anticipent les obstacles
+pub const LOOK_AHEAD: f32 = 32.0;
+
+// Comportements des robots dont le rôle n'en désigne pas
+pub const DEFAULT_STEERING: &WeightedSteering = &[
+    (1.0, Steering::Wander),
+    (
+        2.0,
+        Steering::ObstacleAvoidance {
+            look_ahead: LOOK_AHEAD,
+        },
+    ),
+];
+
+// Ensemble de comportements propre à un robot, tiré de la configuration de son rôle
+#[derive(Component, Clone, Debug, PartialEq)]
+pub struct SteeringSet(pub Vec<(f32, Steering)>);
+
+impl Steering {
+    pub fn needs_neighbours(&self) -> bool {
+        matches!(
+            self,
+            Steering::FleeNeighbour(_)
+                | Steering::PursueNeighbour
+                | Steering::EvadeNeighbour(_)
+                | Steering::Separation(_)
+                | Steering::Alignment
+                | Steering::Cohesion
+        )
+    }
+
+    pub fn force(&self, agent: &Agent, observation: &Observation, rng: &mut dyn RngCore) -> Vec2 {
+        let positions = || -> Vec<Vec2> {
+            observation
+                .neighbours
+                .iter()
+                .map(|neighbour| neighbour.position)
+                .collect()
+        };
+        let resource = || observation.sensor.and_then(|sensor| sensor.nearest);
+        let nearest = |radius: f32| {
+            observation
+                .neighbours
+                .iter()
+                .map(|neighbour| (neighbour, neighbour.position.distance(agent.position)))
+                .filter(|(_, distance)| *distance < radius)
+                .min_by(|(_, a), (_, b)| a.total_cmp(b))
+                .map(|(neighbour, _)| neighbour)
+        };
+        match self {
+            Steering::Wander => wander(
+                agent,
+                rng,
+                observation.robot.max_turn_rate * observation.delta,
+            ),
+            Steering::SeekResource => resource().map_or(Vec2::ZERO, |target| seek(agent, target)),
+            Steering::ArriveResource(slowing_radius) => {
+                resource().map_or(Vec2::ZERO, |target| arrive(agent, target, *slowing_radius))
+            }
+            Steering::FleeNeighbour(radius) => {
+                nearest(*radius).map_or(Vec2::ZERO, |neighbour| flee(agent, neighbour.position))
+            }
+            Steering::PursueNeighbour => nearest(f32::INFINITY).map_or(Vec2::ZERO, |neighbour| {
+                pursue(agent, neighbour.position, neighbour.velocity)
+            }),
+            Steering::EvadeNeighbour(radius) => nearest(*radius).map_or(Vec2::ZERO, |neighbour| {
+                evade(agent, neighbour.position, neighbour.velocity)
+            }),
+            Steering::ObstacleAvoidance { look_ahead } => avoid_obstacles(
+                agent,
+                observation.map,
+                observation.robot.radius,
+                *look_ahead,
+            ),
+            Steering::Separation(radius) => separation(agent, &positions(), *radius),
+            Steering::Alignment => {
+                let velocities: Vec<Vec2> = observation
+                    .neighbours
+                    .iter()
+                    .map(|neighbour| neighbour.velocity)
+                    .collect();
+                alignment(agent, &velocities)
+            }
+            Steering::Cohesion => cohesion(agent, &positions()),
+        }
+    }
+}
+
+// Force combinée d'un ensemble de comportements pondérés
+pub fn weighted_force(
+    behaviours: &WeightedSteering,
+    agent: &Agent,
+    observation: &Observation,
+    rng: &mut dyn RngCore,
+) -> Vec2 {
+    let forces: Vec<(f32, Vec2)> = behaviours
+        .iter()
+        .map(|(weight, behaviour)| (*weight, behaviour.force(agent, observation, rng)))
+        .collect();
+    blend(&forces)
+}
+
+// Rotation de `direction` vers `target`, limitée à `max_step` radians
+pub fn turn_towards(direction: f32, target: f32, max_step: f32) -> f32 {
+    // Normaliser la différence d'angle entre -PI et PI
+    let angle_diff = (target - direction + std::f32::consts::PI).rem_euclid(std::f32::consts::TAU)
+        - std::f32::consts::PI;
+
+    direction + angle_diff.clamp(-max_step, max_step)
+}
+
+// Vers la cible, à pleine vitesse
+pub fn seek(agent: &Agent, target: Vec2) -> Vec2 {
+    (target - agent.position).normalize_or_zero() * agent.max_speed - agent.velocity
+}
+
+// À l'opposé de la menace, à pleine vitesse
+pub fn flee(agent: &Agent, threat: Vec2) -> Vec2 {
+    (agent.position - threat).normalize_or_zero() * agent.max_speed - agent.velocity
+}
+
+// Vers la cible, en ralentissant à moins de `slowing_radius` pour s'y arrêter
+pub fn arrive(agent: &Agent, target: Vec2, slowing_radius: f32) -> Vec2 {
+    let offset = target - agent.position;
+    let distance = offset.length();
+    let speed = if distance < slowing_radius {
+        agent.max_speed * distance / slowing_radius
+    } else {
+        agent.max_speed
+    };
+    offset.normalize_or_zero() * speed - agent.velocity
+}
+
+// Marche aléatoire : la direction voulue s'écarte au plus de `max_angle` radians
+pub fn wander(agent: &Agent, rng: &mut dyn RngCore, max_angle: f32) -> Vec2 {
+    let angle = rng.gen_range(-max_angle..max_angle);
+    Vec2::from_angle(agent.direction + angle) * agent.max_speed - agent.velocity
+}
+
+// Vers la position où sera une cible mobile
+pub fn pursue(agent: &Agent, position: Vec2, velocity: Vec2) -> Vec2 {
+    seek(agent, agent.predict(position, velocity))
+}
+
+// À l'opposé de la position où sera une menace mobile
+pub fn evade(agent: &Agent, position: Vec2, velocity: Vec2) -> Vec2 {
+    flee(agent, agent.predict(position, velocity))
+}
+
+// Sur le côté libre si un obstacle est à moins de `look_ahead` devant l'agent, d'autant plus
+// fort qu'il est proche. Recule si les deux côtés sont bloqués
+pub fn avoid_obstacles(agent: &Agent, map: &Map, radius: f32, look_ahead: f32) -> Vec2 {
+    let heading = Vec2::from_angle(agent.direction);
+    let Some(hit) = map.sweep_circle(
+        agent.position,
+        agent.position + heading * look_ahead,
+        radius,
+    ) else {
+        return Vec2::ZERO;
+    };
+
+    let urgency = (1.0 - hit) * agent.max_speed;
+    [heading.perp(), -heading.perp()]
+        .into_iter()
+        .find(|side| {
+            let probe = agent.position + (heading + *side).normalize() * look_ahead;
+            map.sweep_circle(agent.position, probe, radius).is_none()
+        })
+        .map_or(-heading * urgency, |side| side * urgency)
+}
+
+// À l'écart des voisins à moins de `radius`, d'autant plus qu'ils sont proches
+pub fn separation(agent: &Agent, neighbours: &[Vec2], radius: f32) -> Vec2 {
+    let away: Vec2 = neighbours
+        .iter()
+        .map(|neighbour| agent.position - *neighbour)
+        .filter(|offset| *offset != Vec2::ZERO && offset.length() < radius)
+        .map(|offset| offset / offset.length_squared())
+        .sum();
+
+    if away == Vec2::ZERO {
+        return Vec2::ZERO;
+    }
+    away.normalize() * agent.max_speed - agent.velocity
+}
+
+// Dans la direction moyenne des voisins
+pub fn alignment(agent: &Agent, velocities: &[Vec2]) -> Vec2 {
+    let heading = velocities
+        .iter()
+        .map(|velocity| velocity.normalize_or_zero())
+        .sum::<Vec2>()
+        .normalize_or_zero();
+
+    if heading == Vec2::ZERO {
+        return Vec2::ZERO;
+    }
+    heading * agent.max_speed - agent.velocity
+}
+
+// Vers le centre des voisins
+pub fn cohesion(agent: &Agent, neighbours: &[Vec2]) -> Vec2 {
+    if neighbours.is_empty() {
+        return Vec2::ZERO;
+    }
+    let center = neighbours.iter().sum::<Vec2>() / neighbours.len() as f32;
+    seek(agent, center)
+}
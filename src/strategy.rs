@@ -0,0 +1,352 @@
+use crate::behaviour_tree::{Action, Leaves, RobotLeaves};
+use crate::common::{DiscoveredResources, SimulationRng};
+use crate::config::{ConfigError, FlockingConfig, RobotConfig, SimulationConfig};
+use crate::map::Map;
+use crate::pathfinding::PathFollower;
+use crate::robot::{Collector, Robot, Role, Sensor};
+use crate::spatial::RobotIndex;
+use crate::state::{BehaviourSet, RobotState};
+use crate::steering::{
+    weighted_force, Agent, Steering, SteeringSet, WeightedSteering, DEFAULT_STEERING, LOOK_AHEAD,
+};
+use bevy::{prelude::*, utils::HashMap};
+use rand::RngCore;
+
+// Stratégies par défaut, si la section de robots n'en désigne pas
+pub const EXPLORER_STRATEGY: &str = "RandomWalk";
+pub const COLLECTOR_STRATEGY: &str = "SeekNearest";
+
+pub struct StrategyPlugin;
+
+impl Plugin for StrategyPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<BehaviorRegistry>();
+        let mut registry = app.world_mut().resource_mut::<BehaviorRegistry>();
+        registry.register(EXPLORER_STRATEGY, || RandomWalk);
+        registry.register(COLLECTOR_STRATEGY, || SeekNearest);
+        app.add_systems(Startup, check_strategies);
+        app.add_systems(
+            FixedUpdate,
+            (attach_strategies, steer)
+                .chain()
+                .in_set(BehaviourSet::Steer),
+        );
+    }
+}
+
+// Ce qu'un robot sait au moment de choisir sa direction
+pub struct Observation<'a> {
+    pub robot: &'a Robot,
+    pub position: Vec2,
+    pub state: RobotState,
+    pub sensor: Option<&'a Sensor>,
+    pub known: &'a DiscoveredResources, // Ressources connues du robot
+    pub config: &'a RobotConfig,        // Section de la configuration du robot
+    pub flocking: &'a FlockingConfig,   // Règles de l'essaim pour le rôle du robot
+    pub map: &'a Map,                   // Carte, pour anticiper les obstacles
+    pub steering: &'a WeightedSteering, // Comportements pondérés du robot
+    pub neighbours: &'a [Neighbour],    // Robots en mouvement à moins de `flocking.range`
+    pub delta: f32,                     // Durée du pas de temps, en secondes
+}
+
+// Robot voisin, tel qu'au pas de temps précédent
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Neighbour {
+    pub entity: Entity,
+    pub position: Vec2,
+    pub velocity: Vec2,
+}
+
+// Choix d'une stratégie pour ce pas de temps, `None` laisse la valeur courante
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Decision {
+    pub heading: Option<f32>, // Direction voulue, en radians
+    pub speed: Option<f32>,   // Fraction de la vitesse maximale, entre 0 et 1
+    pub action: Option<Action>,
+}
+
+// Stratégie de déplacement d'un robot sans chemin à suivre. L'aléa doit venir de `rng`
+// pour que la simulation reste reproductible
+pub trait RobotBehavior: Send + Sync + 'static {
+    fn decide(&mut self, observation: &Observation, rng: &mut dyn RngCore) -> Decision;
+
+    // Les voisins ne sont recherchés que pour les stratégies qui les observent
+    fn needs_neighbours(&self) -> bool {
+        false
+    }
+}
+
+type Factory = Box<dyn Fn() -> Box<dyn RobotBehavior> + Send + Sync>;
+
+// Stratégies disponibles, par nom. Chaque robot reçoit sa propre instance
+#[derive(Resource, Default)]
+pub struct BehaviorRegistry {
+    factories: HashMap<String, Factory>,
+}
+
+impl BehaviorRegistry {
+    // Remplace une stratégie déjà enregistrée sous ce nom
+    pub fn register<B: RobotBehavior>(
+        &mut self,
+        name: &str,
+        factory: impl Fn() -> B + Send + Sync + 'static,
+    ) {
+        self.factories.insert(
+            name.to_string(),
+            Box::new(move || Box::new(factory()) as Box<dyn RobotBehavior>),
+        );
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.factories.contains_key(name)
+    }
+
+    pub fn create(&self, name: &str) -> Option<Box<dyn RobotBehavior>> {
+        self.factories.get(name).map(|factory| factory())
+    }
+
+    // Vérifie que les stratégies désignées par la configuration sont enregistrées
+    pub fn check(&self, config: &SimulationConfig) -> Result<(), ConfigError> {
+        for (section, collector) in [("explorers", false), ("collectors", true)] {
+            if !self.contains(strategy_name(config, collector)) {
+                return Err(ConfigError::Invalid {
+                    field: format!("{section}.strategy"),
+                    reason: "no strategy is registered under this name".to_string(),
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+fn section(config: &SimulationConfig, collector: bool) -> &RobotConfig {
+    if collector {
+        &config.collectors
+    } else {
+        &config.explorers
+    }
+}
+
+fn strategy_name(config: &SimulationConfig, collector: bool) -> &str {
+    let default = if collector {
+        COLLECTOR_STRATEGY
+    } else {
+        EXPLORER_STRATEGY
+    };
+    section(config, collector)
+        .strategy
+        .as_deref()
+        .unwrap_or(default)
+}
+
+// Stratégie d'un robot
+#[derive(Component)]
+pub struct Strategy {
+    pub name: String,
+    behavior: Box<dyn RobotBehavior>,
+}
+
+// Marche aléatoire : légers changements de direction au hasard
+pub struct RandomWalk;
+
+impl RobotBehavior for RandomWalk {
+    fn decide(&mut self, observation: &Observation, rng: &mut dyn RngCore) -> Decision {
+        if !matches!(
+            observation.state,
+            RobotState::Wandering
+                | RobotState::Exploring
+                | RobotState::Returning
+                | RobotState::Collecting
+        ) {
+            return Decision::default();
+        }
+
+        let agent = Agent::new(observation.position, observation.robot);
+        let force = weighted_force(observation.steering, &agent, observation, rng);
+        agent.decide(force, observation.robot.max_turn_rate * observation.delta)
+    }
+}
+
+// Se tourne progressivement vers la ressource que détecte le capteur, marche aléatoire sinon
+pub struct SeekNearest;
+
+impl RobotBehavior for SeekNearest {
+    fn decide(&mut self, observation: &Observation, rng: &mut dyn RngCore) -> Decision {
+        const ROTATION_SPEED: f32 = 2.0;
+
+        if observation.state != RobotState::Approaching {
+            return RandomWalk.decide(observation, rng);
+        }
+        if observation
+            .sensor
+            .and_then(|sensor| sensor.nearest)
+            .is_none()
+        {
+            return Decision::default();
+        }
+
+        let agent = Agent::new(observation.position, observation.robot);
+        let approach = [
+            (1.0, Steering::SeekResource),
+            (
+                2.0,
+                Steering::ObstacleAvoidance {
+                    look_ahead: LOOK_AHEAD,
+                },
+            ),
+        ];
+        let force = weighted_force(&approach, &agent, observation, rng);
+        agent.decide(force, ROTATION_SPEED * observation.delta)
+    }
+}
+
+// Une stratégie inconnue laisserait ses robots sans stratégie, donc immobiles : la
+// simulation s'arrête en erreur. Les plugins ont alors tous enregistré les leurs
+fn check_strategies(
+    registry: Res<BehaviorRegistry>,
+    config: Res<SimulationConfig>,
+    mut exit: EventWriter<AppExit>,
+) {
+    if let Err(error) = registry.check(&config) {
+        error!("{error}");
+        exit.send(AppExit::error());
+    }
+}
+
+// Chaque robot reçoit à son apparition la stratégie de sa section de la configuration,
+// et les comportements pondérés de son rôle s'il y en a
+fn attach_strategies(
+    mut commands: Commands,
+    robots: Query<(Entity, &Role, Has<Collector>), Added<Robot>>,
+    registry: Res<BehaviorRegistry>,
+    config: Res<SimulationConfig>,
+) {
+    for (entity, role, collector) in &robots {
+        let name = strategy_name(&config, collector);
+        if let Some(behavior) = registry.create(name) {
+            commands.entity(entity).insert(Strategy {
+                name: name.to_string(),
+                behavior,
+            });
+        }
+        if let Some(steering) = config.steering.get(&role.0) {
+            commands
+                .entity(entity)
+                .insert(SteeringSet(steering.clone()));
+        }
+    }
+}
+
+// Les robots qui suivent un chemin sont guidés par celui-ci, les autres par leur stratégie
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
+fn steer(
+    mut robots: Query<(
+        Entity,
+        &Transform,
+        &mut Robot,
+        &RobotState,
+        &Role,
+        &Children,
+        &mut Strategy,
+        Option<&SteeringSet>,
+        Has<Collector>,
+        Has<PathFollower>,
+    )>,
+    sensors: Query<&Sensor>,
+    robot_index: Res<RobotIndex>,
+    map: Single<&Map>,
+    mut leaves: RobotLeaves,
+    config: Res<SimulationConfig>,
+    time: Res<Time>,
+    mut rng: ResMut<SimulationRng>,
+) {
+    leaves.clear();
+    let default_flocking = FlockingConfig::default();
+
+    // Vitesses des robots en mouvement, seulement si une stratégie observe ses voisins.
+    // Un robot à quai ou hors service n'est le voisin de personne
+    let needs_neighbours = |strategy: &Strategy, steering: &WeightedSteering| {
+        strategy.behavior.needs_neighbours()
+            || steering
+                .iter()
+                .any(|(_, behaviour)| behaviour.needs_neighbours())
+    };
+    let velocities: HashMap<Entity, Vec2> =
+        if robots.iter().any(|(.., strategy, steering, _, following)| {
+            !following
+                && needs_neighbours(strategy, steering.map_or(DEFAULT_STEERING, |set| &set.0))
+        }) {
+            robots
+                .iter()
+                .filter(|(_, _, _, state, ..)| state.is_moving())
+                .map(|(entity, _, robot, ..)| {
+                    let velocity = Vec2::from_angle(robot.direction) * robot.speed * robot.throttle;
+                    (entity, velocity)
+                })
+                .collect()
+        } else {
+            HashMap::new()
+        };
+
+    for (
+        entity,
+        transform,
+        mut robot,
+        state,
+        role,
+        children,
+        mut strategy,
+        steering,
+        collector,
+        following,
+    ) in robots.iter_mut()
+    {
+        if following {
+            continue;
+        }
+        let position = transform.translation.truncate();
+        let flocking = config.flocking.get(&role.0).unwrap_or(&default_flocking);
+        let steering = steering.map_or(DEFAULT_STEERING, |set| &set.0);
+
+        let neighbours: Vec<Neighbour> = if needs_neighbours(&strategy, steering) {
+            robot_index
+                .within_radius(position, flocking.range)
+                .filter(|(other, _)| *other != entity)
+                .filter_map(|(other, position)| {
+                    Some(Neighbour {
+                        entity: other,
+                        position,
+                        velocity: *velocities.get(&other)?,
+                    })
+                })
+                .collect()
+        } else {
+            vec![]
+        };
+
+        let observation = Observation {
+            robot: &robot,
+            position,
+            state: *state,
+            sensor: children.iter().find_map(|&child| sensors.get(child).ok()),
+            known: leaves.discoveries(entity),
+            config: section(&config, collector),
+            flocking,
+            map: &map,
+            steering,
+            neighbours: &neighbours,
+            delta: time.delta_secs(),
+        };
+        let decision = strategy.behavior.decide(&observation, &mut rng.0);
+
+        if let Some(heading) = decision.heading {
+            robot.direction = heading;
+        }
+        if let Some(speed) = decision.speed {
+            robot.throttle = speed.clamp(0.0, 1.0);
+        }
+        if let Some(action) = decision.action {
+            leaves.action(entity, action);
+        }
+    }
+}
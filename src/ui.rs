@@ -1,4 +1,5 @@
 use crate::common::Score;
+use crate::exploration::Coverage;
 use crate::map::Map;
 use bevy::prelude::*;
 
@@ -25,8 +26,12 @@ fn setup(mut commands: Commands) {
     ));
 }
 
-fn update(score: Res<Score>, mut score_display: Single<&mut Text, With<ScoreDisplay>>) {
-    score_display.0 = format!("Score: {}", score.0);
+fn update(
+    score: Res<Score>,
+    coverage: Res<Coverage>,
+    mut score_display: Single<&mut Text, With<ScoreDisplay>>,
+) {
+    score_display.0 = format!("Score: {}  Coverage: {:.1}%", score.0, coverage.0);
 }
 
 fn pan_view(
@@ -43,24 +48,16 @@ fn pan_view(
     let top_boundary: f32 = map.tile_size as f32 * map.height as f32 - window.height();
     let bottom_boundary: f32 = window.height() / 2.0;
 
-    if keys.pressed(KeyCode::ArrowUp) {
-        if transform.translation.y < top_boundary {
-            transform.translation.y += PAN_SPEED;
-        }
+    if keys.pressed(KeyCode::ArrowUp) && transform.translation.y < top_boundary {
+        transform.translation.y += PAN_SPEED;
     }
-    if keys.pressed(KeyCode::ArrowDown) {
-        if transform.translation.y > bottom_boundary {
-            transform.translation.y -= PAN_SPEED;
-        }
+    if keys.pressed(KeyCode::ArrowDown) && transform.translation.y > bottom_boundary {
+        transform.translation.y -= PAN_SPEED;
     }
-    if keys.pressed(KeyCode::ArrowLeft) {
-        if transform.translation.x > left_boundary {
-            transform.translation.x -= PAN_SPEED;
-        }
+    if keys.pressed(KeyCode::ArrowLeft) && transform.translation.x > left_boundary {
+        transform.translation.x -= PAN_SPEED;
     }
-    if keys.pressed(KeyCode::ArrowRight) {
-        if transform.translation.x < right_boundary {
-            transform.translation.x += PAN_SPEED;
-        }
+    if keys.pressed(KeyCode::ArrowRight) && transform.translation.x < right_boundary {
+        transform.translation.x += PAN_SPEED;
     }
 }
@@ -0,0 +1,76 @@
+mod common;
+
+use bevy::prelude::*;
+use essaim_robots::auction::{award, Bid};
+use essaim_robots::common::Statistics;
+use essaim_robots::config::{AllocationConfig, AllocationStrategy, SimulationConfig};
+use essaim_robots::robot::Target;
+
+// La ressource est identifiée par son abscisse
+fn bid(bidder: u32, x: f32, cost: f32) -> Bid {
+    Bid {
+        bidder: Entity::from_raw(bidder),
+        resource: Entity::from_raw(100 + x as u32),
+        position: Vec2::new(x, 0.0),
+        cost,
+    }
+}
+
+#[test]
+fn lowest_bids_win_one_resource_per_collector() {
+    let bids = vec![
+        bid(0, 10.0, 1.0),
+        bid(0, 20.0, 2.0),
+        bid(1, 10.0, 3.0),
+        bid(1, 20.0, 5.0),
+    ];
+
+    let awards = award(bids, &[], 0.0);
+    assert_eq!(awards.len(), 2);
+    // Le premier collecteur remporte la ressource la moins chère, le second celle qui reste
+    assert_eq!(awards[0].winner, Entity::from_raw(0));
+    assert_eq!(awards[0].position, Vec2::new(10.0, 0.0));
+    assert_eq!(awards[1].winner, Entity::from_raw(1));
+    assert_eq!(awards[1].position, Vec2::new(20.0, 0.0));
+}
+
+#[test]
+fn better_bid_takes_over_beyond_margin() {
+    let holders = [bid(0, 10.0, 5.0)];
+
+    // Une offre à peine meilleure ne justifie pas de changer de collecteur
+    assert!(award(vec![bid(1, 10.0, 4.0)], &holders, 2.0).is_empty());
+
+    let awards = award(vec![bid(1, 10.0, 2.0)], &holders, 2.0);
+    assert_eq!(awards.len(), 1);
+    assert_eq!(awards[0].winner, Entity::from_raw(1));
+    assert_eq!(awards[0].previous, Some(Entity::from_raw(0)));
+}
+
+#[test]
+fn auctions_never_send_two_collectors_to_one_resource() {
+    let mut app = common::simulation(SimulationConfig {
+        allocation: AllocationConfig {
+            strategy: AllocationStrategy::Auction,
+            ..Default::default()
+        },
+        ..Default::default()
+    });
+    common::start(&mut app);
+
+    for _ in 0..1500 {
+        app.update();
+
+        let world = app.world_mut();
+        let targets: Vec<Entity> = world
+            .query::<&Target>()
+            .iter(world)
+            .map(|target| target.resource)
+            .collect();
+        for (index, target) in targets.iter().enumerate() {
+            assert!(!targets[index + 1..].contains(target));
+        }
+    }
+
+    assert!(app.world().resource::<Statistics>().tasks_awarded > 0);
+}
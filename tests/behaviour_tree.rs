@@ -0,0 +1,181 @@
+mod common;
+
+use bevy::prelude::*;
+use essaim_robots::behaviour_tree::{Action, BehaviourTree, Condition, Leaves, Node, Status};
+use essaim_robots::common::{DiscoveredResources, Statistics};
+use essaim_robots::config::{AllocationStrategy, ConfigError, SimulationConfig};
+use essaim_robots::robot::{Collector, Target};
+use std::time::Duration;
+
+// Feuilles scriptées : la cargaison est pleine, les actions retournent `status`
+struct Script {
+    cargo_full: bool,
+    status: Status,
+    actions: Vec<Action>,
+}
+
+impl Script {
+    fn new(cargo_full: bool, status: Status) -> Self {
+        Self {
+            cargo_full,
+            status,
+            actions: vec![],
+        }
+    }
+}
+
+impl Leaves for Script {
+    fn condition(&mut self, _: Entity, condition: Condition) -> bool {
+        condition == Condition::CargoFull && self.cargo_full
+    }
+
+    fn action(&mut self, _: Entity, action: Action) -> Status {
+        self.actions.push(action);
+        self.status
+    }
+}
+
+fn collector_tree() -> Node {
+    Node::Selector(vec![
+        Node::Sequence(vec![
+            Node::Condition(Condition::CargoFull),
+            Node::Action(Action::ReturnToBase),
+        ]),
+        Node::Action(Action::Collect),
+    ])
+}
+
+#[test]
+fn selector_runs_the_first_branch_that_does_not_fail() {
+    let robot = Entity::from_raw(0);
+    let mut tree = BehaviourTree::new(collector_tree());
+
+    let mut full = Script::new(true, Status::Running);
+    assert_eq!(tree.tick(robot, &mut full, Duration::ZERO), Status::Running);
+    assert_eq!(full.actions, [Action::ReturnToBase]);
+
+    // La séquence échoue sur sa condition : le sélecteur passe à la branche suivante
+    let mut empty = Script::new(false, Status::Success);
+    assert_eq!(
+        tree.tick(robot, &mut empty, Duration::ZERO),
+        Status::Success
+    );
+    assert_eq!(empty.actions, [Action::Collect]);
+}
+
+#[test]
+fn cooldown_skips_its_child_after_a_result() {
+    let robot = Entity::from_raw(0);
+    let mut tree = BehaviourTree::new(Node::Selector(vec![
+        Node::Cooldown(1.0, Box::new(Node::Action(Action::Explore))),
+        Node::Action(Action::Wander),
+    ]));
+
+    let mut script = Script::new(false, Status::Failure);
+    tree.tick(robot, &mut script, Duration::ZERO);
+    tree.tick(robot, &mut script, Duration::from_millis(500));
+    tree.tick(robot, &mut script, Duration::from_secs(1));
+    assert_eq!(
+        script.actions,
+        [
+            Action::Explore,
+            Action::Wander,
+            Action::Wander,
+            Action::Explore,
+            Action::Wander
+        ]
+    );
+}
+
+#[test]
+fn parallel_needs_its_threshold_of_successes() {
+    let robot = Entity::from_raw(0);
+    let children = || {
+        vec![
+            Node::Condition(Condition::CargoFull),
+            Node::Action(Action::Collect),
+        ]
+    };
+
+    let mut one = BehaviourTree::new(Node::Parallel(1, children()));
+    let mut both = BehaviourTree::new(Node::Parallel(2, children()));
+    let mut script = Script::new(true, Status::Running);
+
+    assert_eq!(
+        one.tick(robot, &mut script, Duration::ZERO),
+        Status::Success
+    );
+    assert_eq!(
+        both.tick(robot, &mut script, Duration::ZERO),
+        Status::Running
+    );
+    // Les deux enfants sont exécutés à chaque passage
+    assert_eq!(script.actions, [Action::Collect, Action::Collect]);
+}
+
+#[test]
+fn trees_are_read_from_the_configuration() {
+    let config = SimulationConfig::load("behaviours.ron").unwrap();
+    assert!(config.behaviours.contains_key("Explorer"));
+    assert!(config.behaviours.contains_key("Scavenger"));
+
+    let error = SimulationConfig::parse(
+        "(collectors: (count: 2, radius: 10.0, speed: 50.0, max_turn_rate: 10.0, \
+         max_deviation: 1.0, sensor_range: 100, capacity: 3, role: Some(\"Miner\")))",
+    )
+    .unwrap_err();
+    match error {
+        ConfigError::Invalid { field, .. } => assert_eq!(field, "collectors.role"),
+        other => panic!("unexpected error: {other}"),
+    }
+
+    let error = SimulationConfig::parse(
+        "(behaviours: { \"Collector\": Parallel(3, [Action(Collect), Action(Wander)]) })",
+    )
+    .unwrap_err();
+    match error {
+        ConfigError::Invalid { field, .. } => assert_eq!(field, "behaviours.Collector"),
+        other => panic!("unexpected error: {other}"),
+    }
+}
+
+#[test]
+fn tree_replaces_the_built_in_collector_behaviour() {
+    let mut app = common::simulation(
+        SimulationConfig::parse("(behaviours: { \"Collector\": Action(Wander) })").unwrap(),
+    );
+    common::start(&mut app);
+
+    for _ in 0..1500 {
+        app.update();
+    }
+
+    // Des ressources sont connues, mais l'arbre ne fait qu'errer
+    let world = app.world_mut();
+    assert!(!world.resource::<DiscoveredResources>().0.is_empty());
+    let collectors: Vec<(bool, bool)> = world
+        .query_filtered::<(Has<BehaviourTree>, Has<Target>), With<Collector>>()
+        .iter(world)
+        .collect();
+    assert_eq!(collectors.len(), 2);
+    assert!(collectors
+        .iter()
+        .all(|&(has_tree, has_target)| has_tree && !has_target));
+}
+
+#[test]
+fn tree_driven_collectors_do_not_bid_in_auctions() {
+    let mut config =
+        SimulationConfig::parse("(behaviours: { \"Collector\": Action(Wander) })").unwrap();
+    config.allocation.strategy = AllocationStrategy::Auction;
+    let mut app = common::simulation(config);
+    common::start(&mut app);
+
+    for _ in 0..1500 {
+        app.update();
+    }
+
+    let world = app.world();
+    assert!(!world.resource::<DiscoveredResources>().0.is_empty());
+    assert_eq!(world.resource::<Statistics>().tasks_awarded, 0);
+}
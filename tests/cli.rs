@@ -0,0 +1,46 @@
+use essaim_robots::cli::{Cli, CliError};
+
+fn args(line: &str) -> Vec<String> {
+    line.split_whitespace().map(String::from).collect()
+}
+
+#[test]
+fn overrides_apply_on_top_of_config_file() {
+    let cli = Cli::parse(args(
+        "--config config.ron --seed 12 --explorers 5 --collectors 0 \
+         --map-width 64 --map-height 32 --duration 30 --headless",
+    ))
+    .unwrap();
+    let config = cli.config().unwrap();
+
+    assert!(cli.headless);
+    assert_eq!(config.seed, 12);
+    assert_eq!(config.explorers.count, 5);
+    assert_eq!(config.collectors.count, 0);
+    assert_eq!(config.map.width, 64);
+    assert_eq!(config.map.height, 32);
+    assert_eq!(config.duration, Some(30.0));
+}
+
+#[test]
+fn bad_arguments_are_reported() {
+    assert!(matches!(
+        Cli::parse(args("--seed")),
+        Err(CliError::MissingValue(_))
+    ));
+    assert!(matches!(
+        Cli::parse(args("--explorers many")),
+        Err(CliError::InvalidValue { .. })
+    ));
+    assert!(matches!(
+        Cli::parse(args("--fast")),
+        Err(CliError::UnknownArgument(_))
+    ));
+}
+
+#[test]
+fn overrides_are_validated() {
+    let cli = Cli::parse(args("--map-width 0")).unwrap();
+
+    assert!(matches!(cli.config(), Err(CliError::Config(_))));
+}
@@ -0,0 +1,238 @@
+mod common;
+
+use bevy::math::{ivec2, uvec2};
+use bevy::prelude::*;
+use essaim_robots::common::{
+    Base, DiscoveredResources, Discovery, GameResource, ResourceCollectedEvent, ResourceType, Score,
+};
+use essaim_robots::config::{BatteryConfig, SimulationConfig};
+use essaim_robots::map::Map;
+use essaim_robots::robot::{Cargo, Collector, Returning, Robot, Target};
+
+// Nombre de ResourceCollectedEvent reçus
+#[derive(Resource, Default)]
+struct Collected(usize);
+
+fn count_collected(
+    mut events: EventReader<ResourceCollectedEvent>,
+    mut collected: ResMut<Collected>,
+) {
+    collected.0 += events.read().count();
+}
+
+fn resource_count(app: &mut App) -> usize {
+    let world = app.world_mut();
+    world.query::<&GameResource>().iter(world).count()
+}
+
+fn carried(app: &mut App) -> usize {
+    let world = app.world_mut();
+    world
+        .query::<&Cargo>()
+        .iter(world)
+        .map(|cargo| cargo.items.len())
+        .sum()
+}
+
+// Simulation sans ressource sur la carte ni batterie, pour placer les découvertes à la main
+fn empty_simulation(collectors: usize) -> App {
+    let mut config = SimulationConfig {
+        battery: BatteryConfig {
+            enabled: false,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    config.resources.count = 0;
+    config.collectors.count = collectors;
+    let mut app = common::simulation(config);
+    common::start(&mut app);
+    app.update();
+    app
+}
+
+// Fait découvrir une ressource sur la case praticable la plus proche de `cell`, entourée
+// d'obstacles si `enclosed`
+fn discover(app: &mut App, cell: UVec2, enclosed: bool) -> Entity {
+    let world = app.world_mut();
+    let mut map = world.query::<&mut Map>().single_mut(world);
+    let cell = map.nearest_walkable(cell).unwrap();
+    if enclosed {
+        for dy in -1..=1 {
+            for dx in -1..=1 {
+                if dx != 0 || dy != 0 {
+                    map.set_obstacle((cell.as_ivec2() + ivec2(dx, dy)).as_uvec2(), true);
+                }
+            }
+        }
+    }
+    let position = map.cell_center(cell);
+
+    let resource = world
+        .spawn((
+            GameResource::new(ResourceType::Mineral),
+            Transform::from_translation(position.extend(0.0)),
+        ))
+        .id();
+    world
+        .resource_mut::<DiscoveredResources>()
+        .insert(resource, position);
+    resource
+}
+
+fn claimants(app: &mut App, resource: Entity) -> Vec<Entity> {
+    let world = app.world_mut();
+    world
+        .query::<(Entity, &Target)>()
+        .iter(world)
+        .filter(|(_, target)| target.resource == resource)
+        .map(|(entity, _)| entity)
+        .collect()
+}
+
+fn discovery(app: &App, resource: Entity) -> Discovery {
+    app.world()
+        .resource::<DiscoveredResources>()
+        .0
+        .iter()
+        .find(|discovery| discovery.resource == resource)
+        .cloned()
+        .unwrap()
+}
+
+#[test]
+fn a_discovery_is_claimed_by_a_single_collector() {
+    let mut app = empty_simulation(4);
+    let resource = discover(&mut app, uvec2(20, 20), false);
+
+    for _ in 0..20 {
+        app.update();
+        let claimants = claimants(&mut app, resource);
+        assert_eq!(claimants.len(), 1);
+        assert_eq!(discovery(&app, resource).claimed_by, Some(claimants[0]));
+    }
+}
+
+#[test]
+fn an_unreachable_discovery_is_released() {
+    let mut app = empty_simulation(1);
+    let resource = discover(&mut app, uvec2(20, 20), true);
+
+    app.update();
+    let collector = claimants(&mut app, resource)[0];
+
+    for _ in 0..100 {
+        app.update();
+        if claimants(&mut app, resource).is_empty() {
+            break;
+        }
+    }
+    assert!(claimants(&mut app, resource).is_empty());
+    let discovery = discovery(&app, resource);
+    assert_eq!(discovery.claimed_by, None);
+    assert_eq!(discovery.unreachable_for, [collector]);
+
+    // Le collecteur ne la réclame plus
+    for _ in 0..20 {
+        app.update();
+    }
+    assert!(claimants(&mut app, resource).is_empty());
+}
+
+#[test]
+fn a_full_collector_returns_and_scores_on_unloading() {
+    let mut app = empty_simulation(1);
+
+    // Collecteur plein, posé loin de la base
+    let world = app.world_mut();
+    let map = world.query::<&Map>().single(world);
+    let position = map.cell_center(map.nearest_walkable(uvec2(12, 12)).unwrap());
+    let base = world.query::<(&Transform, &Base)>().single(world);
+    let (base_position, base_radius) = (base.0.translation.truncate(), base.1.radius);
+    let (collector, mut transform, mut cargo) = world
+        .query_filtered::<(Entity, &mut Transform, &mut Cargo), With<Collector>>()
+        .single_mut(world);
+    transform.translation = position.extend(transform.translation.z);
+    while !cargo.is_full() {
+        cargo.items.push(GameResource::new(ResourceType::Mineral));
+    }
+    let points: u32 = cargo.items.iter().map(|item| item.points).sum();
+
+    app.update();
+    assert!(app.world().get::<Returning>(collector).is_some());
+
+    let mut ticks = 0;
+    while carried(&mut app) > 0 {
+        assert_eq!(app.world().resource::<Score>().0, 0);
+        app.update();
+        ticks += 1;
+        assert!(ticks < 3000, "the collector never reached the base");
+    }
+
+    let world = app.world();
+    let position = world.get::<Transform>(collector).unwrap().translation;
+    assert!(position.truncate().distance(base_position) < base_radius);
+    assert_eq!(world.resource::<Score>().0, points);
+    assert!(world.get::<Returning>(collector).is_none());
+}
+
+#[test]
+fn overlapping_collectors_collect_a_resource_once() {
+    let mut app = common::simulation(SimulationConfig {
+        battery: BatteryConfig {
+            enabled: false,
+            ..Default::default()
+        },
+        ..Default::default()
+    });
+    app.init_resource::<Collected>()
+        .add_systems(PostUpdate, count_collected);
+    common::start(&mut app);
+    app.update();
+
+    // Les deux collecteurs sont posés, immobiles, sur la même ressource
+    let world = app.world_mut();
+    let resource = world
+        .query_filtered::<&Transform, With<GameResource>>()
+        .iter(world)
+        .next()
+        .expect("the map has resources")
+        .translation;
+    let mut collectors = world.query_filtered::<(&mut Transform, &mut Robot), With<Collector>>();
+    for (mut transform, mut robot) in collectors.iter_mut(world) {
+        transform.translation = resource.with_z(transform.translation.z);
+        robot.speed = 0.0;
+    }
+
+    let before = resource_count(&mut app) + carried(&mut app);
+    for _ in 0..5 {
+        app.update();
+    }
+
+    assert_eq!(carried(&mut app), 1);
+    assert_eq!(resource_count(&mut app) + carried(&mut app), before);
+
+    // Déchargée à la base, elle n'est comptée qu'une fois
+    let world = app.world_mut();
+    let points: u32 = world
+        .query::<&Cargo>()
+        .iter(world)
+        .flat_map(|cargo| &cargo.items)
+        .map(|item| item.points)
+        .sum();
+    let base = world
+        .query_filtered::<&Transform, With<Base>>()
+        .single(world)
+        .translation;
+    for (mut transform, _) in collectors.iter_mut(world) {
+        transform.translation = base.with_z(transform.translation.z);
+    }
+    for _ in 0..5 {
+        app.update();
+    }
+
+    assert_eq!(carried(&mut app), 0);
+    let world = app.world();
+    assert_eq!(world.resource::<Collected>().0, 1);
+    assert_eq!(world.resource::<Score>().0, points);
+}
@@ -0,0 +1,124 @@
+mod common;
+
+use bevy::prelude::*;
+use essaim_robots::collision::overlap;
+use essaim_robots::common::{Base, Statistics};
+use essaim_robots::config::SimulationConfig;
+use essaim_robots::energy::{Battery, Recharging};
+use essaim_robots::robot::Robot;
+use essaim_robots::state::RobotState;
+
+#[test]
+fn overlap_gives_direction_and_depth() {
+    assert_eq!(
+        overlap(Vec2::ZERO, 5.0, Vec2::new(8.0, 0.0), 5.0),
+        Some((Vec2::X, 2.0))
+    );
+    assert_eq!(overlap(Vec2::ZERO, 5.0, Vec2::new(0.0, 10.0), 5.0), None);
+    // Au même endroit, la direction reste définie
+    assert_eq!(
+        overlap(Vec2::ONE, 5.0, Vec2::ONE, 5.0),
+        Some((Vec2::X, 10.0))
+    );
+}
+
+// Plus grand chevauchement entre deux robots au fil de la simulation, dès le premier pas
+fn deepest_overlap(robots: bool, ticks: usize) -> (f32, u32) {
+    let mut config = SimulationConfig::default();
+    config.explorers.count = 6;
+    config.collectors.count = 6;
+    config.collisions.robots = robots;
+
+    let mut app = common::simulation(config);
+    common::start(&mut app);
+
+    let mut deepest: f32 = 0.0;
+    for _ in 0..ticks {
+        app.update();
+
+        let world = app.world_mut();
+        let bodies: Vec<(Vec2, f32)> = world
+            .query::<(&Transform, &Robot)>()
+            .iter(world)
+            .map(|(transform, robot)| (transform.translation.truncate(), robot.radius))
+            .collect();
+        for (i, a) in bodies.iter().enumerate() {
+            for b in &bodies[i + 1..] {
+                if let Some((_, depth)) = overlap(a.0, a.1, b.0, b.1) {
+                    deepest = deepest.max(depth);
+                }
+            }
+        }
+    }
+
+    (
+        deepest,
+        app.world().resource::<Statistics>().robot_collisions,
+    )
+}
+
+#[test]
+fn robots_do_not_pass_through_each_other() {
+    let (deepest, contacts) = deepest_overlap(true, 1200);
+    assert!(deepest < 0.05, "robots overlap by {deepest}");
+    assert!(contacts > 0);
+
+    // Sans collisions, tous les robots partent du même endroit
+    let (deepest, contacts) = deepest_overlap(false, 1200);
+    assert!(deepest > 1.0, "robots overlap by {deepest}");
+    assert_eq!(contacts, 0);
+}
+
+#[test]
+fn docked_robots_are_spread_and_keep_charging() {
+    let mut config = SimulationConfig::default();
+    config.battery.enabled = true;
+
+    let mut app = common::simulation(config);
+    common::start(&mut app);
+    app.update();
+
+    // Deux robots à quai au même endroit, au centre de la base
+    let world = app.world_mut();
+    let base = world
+        .query_filtered::<&Transform, With<Base>>()
+        .single(world)
+        .translation;
+    let docked: Vec<Entity> = world
+        .query_filtered::<Entity, With<Robot>>()
+        .iter(world)
+        .take(2)
+        .collect();
+    for &robot in &docked {
+        let mut entity = world.entity_mut(robot);
+        entity.get_mut::<Transform>().unwrap().translation = base.with_z(1.0);
+        entity.get_mut::<Battery>().unwrap().charge = 1.0;
+        entity.insert(Recharging { docked: true });
+    }
+
+    for _ in 0..10 {
+        app.update();
+    }
+
+    let world = app.world_mut();
+    let bodies: Vec<(Vec2, f32, RobotState, f32)> = docked
+        .iter()
+        .map(|&robot| {
+            let entity = world.entity(robot);
+            (
+                entity.get::<Transform>().unwrap().translation.truncate(),
+                entity.get::<Robot>().unwrap().radius,
+                *entity.get::<RobotState>().unwrap(),
+                entity.get::<Battery>().unwrap().charge,
+            )
+        })
+        .collect();
+    assert_eq!(
+        overlap(bodies[0].0, bodies[0].1, bodies[1].0, bodies[1].1),
+        None
+    );
+    for (_, _, state, charge) in bodies {
+        assert_eq!(state, RobotState::Charging);
+        assert!(charge > 1.0);
+    }
+}
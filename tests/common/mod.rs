@@ -0,0 +1,19 @@
+use bevy::prelude::*;
+use essaim_robots::config::SimulationConfig;
+use essaim_robots::headless::HeadlessPlugin;
+use essaim_robots::SimulationPlugins;
+
+// Simulation complète sans fenêtre, comme `--headless` : chaque `update` avance d'un pas de
+// temps fixe. Le test peut encore ajouter ses systèmes avant `start`
+pub fn simulation(config: SimulationConfig) -> App {
+    let mut app = App::new();
+    app.add_plugins((HeadlessPlugin, SimulationPlugins))
+        .insert_resource(config);
+    app
+}
+
+// Termine la construction de l'application
+pub fn start(app: &mut App) {
+    app.finish();
+    app.cleanup();
+}
@@ -0,0 +1,184 @@
+use bevy::prelude::*;
+use bevy::time::TimeUpdateStrategy;
+use essaim_robots::common::{CommonPlugin, ResourceType, Statistics};
+use essaim_robots::comms::{
+    CommsPlugin, IncomingMessage, MessagingSet, OutgoingMessage, RobotMessage,
+};
+use essaim_robots::config::{CommsConfig, SimulationConfig};
+use essaim_robots::energy::Disabled;
+use essaim_robots::robot::Robot;
+use essaim_robots::spatial::SpatialPlugin;
+
+#[derive(Resource, Default)]
+struct Received(Vec<IncomingMessage>);
+
+fn record(mut events: EventReader<IncomingMessage>, mut received: ResMut<Received>) {
+    received.0.extend(events.read().cloned());
+}
+
+// Liaison parfaite, sans annonce de position pendant le test
+fn comms() -> CommsConfig {
+    CommsConfig {
+        range: 100.0,
+        position_interval: 1000.0,
+        ..Default::default()
+    }
+}
+
+fn app(comms: CommsConfig) -> App {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, CommonPlugin, SpatialPlugin, CommsPlugin))
+        .insert_resource(SimulationConfig {
+            comms,
+            ..Default::default()
+        })
+        .insert_resource(TimeUpdateStrategy::ManualDuration(
+            Time::<Fixed>::default().timestep(),
+        ))
+        .init_resource::<Received>()
+        .add_systems(FixedUpdate, record.after(MessagingSet));
+    app
+}
+
+fn spawn_robot(app: &mut App, x: f32) -> Entity {
+    app.world_mut()
+        .spawn((
+            Transform::from_xyz(x, 0.0, 1.0),
+            Robot {
+                direction: 0.0,
+                radius: 5.0,
+                speed: 0.0,
+                throttle: 1.0,
+                max_turn_rate: 1.0,
+                max_deviation: 1.0,
+            },
+        ))
+        .id()
+}
+
+fn broadcast(app: &mut App, sender: Entity, count: usize) {
+    for _ in 0..count {
+        app.world_mut().send_event(OutgoingMessage {
+            sender,
+            position: Vec2::ZERO,
+            message: RobotMessage::Position(None),
+        });
+    }
+}
+
+fn run(app: &mut App, ticks: usize) {
+    for _ in 0..ticks {
+        app.update();
+    }
+}
+
+#[test]
+fn messages_only_reach_robots_in_range() {
+    let mut app = app(comms());
+    let sender = spawn_robot(&mut app, 0.0);
+    let near = spawn_robot(&mut app, 60.0);
+    let _far = spawn_robot(&mut app, 300.0);
+    let disabled = spawn_robot(&mut app, 20.0);
+    app.world_mut().entity_mut(disabled).insert(Disabled);
+
+    run(&mut app, 3);
+
+    let message = RobotMessage::Discovery {
+        resource: Entity::from_raw(99),
+        position: Vec2::new(10.0, 10.0),
+        kind: ResourceType::Mineral,
+    };
+    app.world_mut().send_event(OutgoingMessage {
+        sender,
+        position: Vec2::ZERO,
+        message: message.clone(),
+    });
+    run(&mut app, 3);
+
+    let received = &app.world().resource::<Received>().0;
+    assert_eq!(received.len(), 1);
+    assert_eq!(received[0].receiver, near);
+    assert_eq!(received[0].sender, sender);
+    assert_eq!(received[0].message, message);
+}
+
+#[test]
+fn robots_announce_their_position() {
+    let mut app = app(CommsConfig {
+        position_interval: 0.05,
+        ..comms()
+    });
+    let first = spawn_robot(&mut app, 0.0);
+    let second = spawn_robot(&mut app, 50.0);
+
+    run(&mut app, 10);
+
+    let received = &app.world().resource::<Received>().0;
+    assert!(received.iter().any(|incoming| incoming.receiver == first
+        && incoming.sender == second
+        && incoming.message == RobotMessage::Position(None)
+        && incoming.sender_position == Vec2::new(50.0, 0.0)));
+    assert!(received
+        .iter()
+        .all(|incoming| incoming.receiver != incoming.sender));
+}
+
+#[test]
+fn latency_delays_delivery() {
+    let mut app = app(CommsConfig {
+        latency: 0.5,
+        ..comms()
+    });
+    let sender = spawn_robot(&mut app, 0.0);
+    spawn_robot(&mut app, 50.0);
+    run(&mut app, 3);
+
+    broadcast(&mut app, sender, 1);
+    run(&mut app, 16);
+    assert!(app.world().resource::<Received>().0.is_empty());
+
+    // Le pas fixe par défaut est de 1/64 s
+    run(&mut app, 24);
+    assert_eq!(app.world().resource::<Received>().0.len(), 1);
+}
+
+#[test]
+fn messages_over_bandwidth_are_dropped() {
+    let mut app = app(CommsConfig {
+        bandwidth: Some(2),
+        ..comms()
+    });
+    let sender = spawn_robot(&mut app, 0.0);
+    spawn_robot(&mut app, 50.0);
+    spawn_robot(&mut app, -50.0);
+    run(&mut app, 3);
+
+    broadcast(&mut app, sender, 3);
+    run(&mut app, 3);
+
+    let statistics = app.world().resource::<Statistics>();
+    // Chaque copie est comptée, une par destinataire
+    assert_eq!(statistics.messages_sent, 6);
+    assert_eq!(statistics.messages_delivered, 4);
+    assert_eq!(statistics.messages_dropped, 2);
+}
+
+#[test]
+fn lost_messages_are_counted() {
+    let mut app = app(CommsConfig {
+        loss: 1.0,
+        ..comms()
+    });
+    let sender = spawn_robot(&mut app, 0.0);
+    spawn_robot(&mut app, 50.0);
+    run(&mut app, 3);
+
+    broadcast(&mut app, sender, 5);
+    run(&mut app, 3);
+
+    let statistics = app.world().resource::<Statistics>();
+    assert_eq!(statistics.messages_sent, 5);
+    assert_eq!(statistics.messages_delivered, 0);
+    assert_eq!(statistics.messages_dropped, 5);
+    assert!(app.world().resource::<Received>().0.is_empty());
+}
@@ -0,0 +1,58 @@
+use essaim_robots::config::{ConfigError, SimulationConfig};
+
+#[test]
+fn missing_fields_keep_defaults() {
+    let config = SimulationConfig::parse("(seed: 42, map: (width: 100))").unwrap();
+
+    assert_eq!(config.seed, 42);
+    assert_eq!(config.map.width, 100);
+    assert_eq!(config.map.height, SimulationConfig::default().map.height);
+    assert_eq!(config.collectors.count, 2);
+}
+
+#[test]
+fn repository_config_matches_defaults() {
+    let config = SimulationConfig::load("config.ron").unwrap();
+    let defaults = SimulationConfig::default();
+
+    assert_eq!(config.seed, defaults.seed);
+    assert_eq!(config.map.width, defaults.map.width);
+    assert_eq!(config.resources.count, defaults.resources.count);
+    assert_eq!(config.explorers.speed, defaults.explorers.speed);
+    assert_eq!(
+        config.collectors.sensor_range,
+        defaults.collectors.sensor_range
+    );
+}
+
+#[test]
+fn robots_collide_unless_switched_off() {
+    let config = SimulationConfig::load("config.ron").unwrap();
+    assert!(config.collisions.robots);
+    assert!(SimulationConfig::default().collisions.robots);
+
+    let config = SimulationConfig::parse("(collisions: (robots: false))").unwrap();
+    assert!(!config.collisions.robots);
+}
+
+#[test]
+fn invalid_value_names_the_field() {
+    let error = SimulationConfig::parse(
+        "(collectors: (count: 2, radius: 10.0, speed: -1.0, max_turn_rate: 10.0, \
+         max_deviation: 1.0, sensor_range: 100))",
+    )
+    .unwrap_err();
+
+    match error {
+        ConfigError::Invalid { field, .. } => assert_eq!(field, "collectors.speed"),
+        other => panic!("unexpected error: {other}"),
+    }
+}
+
+#[test]
+fn unknown_field_is_rejected() {
+    let error = SimulationConfig::parse("(map: (widht: 100))").unwrap_err();
+
+    assert!(matches!(error, ConfigError::Parse(_)));
+    assert!(error.to_string().contains("widht"));
+}
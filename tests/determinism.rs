@@ -0,0 +1,59 @@
+mod common;
+
+use bevy::prelude::*;
+use essaim_robots::common::Score;
+use essaim_robots::config::SimulationConfig;
+use essaim_robots::robot::Robot;
+
+const TICKS: usize = 1000;
+
+fn run(seed: u32) -> (u32, Vec<Vec3>) {
+    let mut app = common::simulation(SimulationConfig {
+        seed,
+        ..Default::default()
+    });
+    common::start(&mut app);
+    app.update();
+    let start = positions(&mut app);
+
+    for _ in 0..TICKS {
+        app.update();
+    }
+
+    let score = app.world().resource::<Score>().0;
+    let end = positions(&mut app);
+
+    // Deux runs immobiles seraient identiques sans rien prouver
+    let travelled: f32 = start.iter().zip(&end).map(|(a, b)| a.distance(*b)).sum();
+    assert!(travelled > 0.0, "les robots n'ont pas bougé");
+
+    (score, end)
+}
+
+fn positions(app: &mut App) -> Vec<Vec3> {
+    let world = app.world_mut();
+    world
+        .query_filtered::<&Transform, With<Robot>>()
+        .iter(world)
+        .map(|transform| transform.translation)
+        .collect()
+}
+
+#[test]
+fn same_seed_gives_identical_runs() {
+    let (first_score, first_positions) = run(7);
+    let (second_score, second_positions) = run(7);
+
+    assert!(first_score > 0, "aucune ressource déchargée");
+
+    assert_eq!(first_score, second_score);
+    assert_eq!(first_positions, second_positions);
+}
+
+#[test]
+fn different_seeds_diverge() {
+    let (_, first_positions) = run(7);
+    let (_, second_positions) = run(8);
+
+    assert_ne!(first_positions, second_positions);
+}
@@ -0,0 +1,188 @@
+mod common;
+
+use bevy::math::{bounding::Aabb2d, uvec2};
+use bevy::prelude::*;
+use essaim_robots::common::{Collider, GameResource, ResourceType, Score, Statistics};
+use essaim_robots::config::{BatteryConfig, SimulationConfig};
+use essaim_robots::energy::{Battery, Disabled, Recharging};
+use essaim_robots::map::Map;
+use essaim_robots::pathfinding::PathFollower;
+use essaim_robots::robot::{Cargo, Collector, Robot};
+
+fn app(battery: BatteryConfig) -> App {
+    let mut app = common::simulation(SimulationConfig {
+        battery,
+        ..Default::default()
+    });
+    common::start(&mut app);
+    app
+}
+
+fn robot_positions(app: &mut App) -> Vec<Vec3> {
+    let world = app.world_mut();
+    world
+        .query_filtered::<&Transform, With<Robot>>()
+        .iter(world)
+        .map(|transform| transform.translation)
+        .collect()
+}
+
+#[test]
+fn recharge_is_capped_at_capacity() {
+    let mut battery = Battery::new(10.0);
+    battery.charge = 4.0;
+
+    battery.recharge(3.0);
+    assert_eq!(battery.charge, 7.0);
+    battery.recharge(100.0);
+    assert_eq!(battery.charge, 10.0);
+    assert_eq!(battery.level(), 1.0);
+}
+
+#[test]
+fn empty_battery_disables_robot() {
+    let mut app = app(BatteryConfig {
+        enabled: true,
+        capacity: 1.0,
+        low_threshold: 0.0,
+        ..Default::default()
+    });
+
+    for _ in 0..400 {
+        app.update();
+    }
+
+    let world = app.world_mut();
+    let robots = world
+        .query_filtered::<(), With<Robot>>()
+        .iter(world)
+        .count();
+    let disabled = world
+        .query_filtered::<(), With<Disabled>>()
+        .iter(world)
+        .count();
+    assert_eq!(disabled, robots);
+    assert_eq!(
+        world.resource::<Statistics>().robots_disabled as usize,
+        robots
+    );
+
+    // Un robot hors service ne bouge plus
+    let before = robot_positions(&mut app);
+    for _ in 0..10 {
+        app.update();
+    }
+    assert_eq!(robot_positions(&mut app), before);
+}
+
+// Premier collecteur, posé loin de la base avec `charge` dans sa batterie
+fn collector_away_from_base(app: &mut App, charge: f32) -> Entity {
+    app.update();
+    let world = app.world_mut();
+    let map = world.query::<&Map>().single(world);
+    let position = map.cell_center(map.nearest_walkable(uvec2(12, 12)).unwrap());
+    let (collector, mut transform, mut battery) = world
+        .query_filtered::<(Entity, &mut Transform, &mut Battery), With<Collector>>()
+        .iter_mut(world)
+        .next()
+        .unwrap();
+    transform.translation = position.extend(transform.translation.z);
+    battery.charge = charge;
+    collector
+}
+
+#[test]
+fn low_battery_returns_to_charge_at_base() {
+    let mut app = app(BatteryConfig {
+        enabled: true,
+        ..Default::default()
+    });
+    let collector = collector_away_from_base(&mut app, 10.0);
+
+    app.update();
+    let world = app.world();
+    assert!(world
+        .get::<Recharging>(collector)
+        .is_some_and(|recharging| !recharging.docked));
+    assert!(world.get::<PathFollower>(collector).is_some());
+
+    // À quai, il se recharge entièrement puis reprend sa tâche
+    let mut docked = false;
+    for _ in 0..3000 {
+        app.update();
+        let world = app.world();
+        match world.get::<Recharging>(collector) {
+            Some(recharging) => docked |= recharging.docked,
+            None => break,
+        }
+    }
+    let world = app.world();
+    assert!(docked);
+    assert!(world.get::<Recharging>(collector).is_none());
+    assert!(world.get::<Disabled>(collector).is_none());
+    let battery = world.get::<Battery>(collector).unwrap();
+    assert_eq!(battery.charge, battery.capacity);
+}
+
+#[test]
+fn energy_pickup_recharges_instead_of_carrying() {
+    let mut app = app(BatteryConfig {
+        enabled: true,
+        energy_pickup: Some(25.0),
+        ..Default::default()
+    });
+    let collector = collector_away_from_base(&mut app, 50.0);
+
+    // Une ressource d'énergie et un minerai sous le collecteur immobile
+    let world = app.world_mut();
+    let position = world.get::<Transform>(collector).unwrap().translation;
+    world.get_mut::<Robot>(collector).unwrap().speed = 0.0;
+    for kind in [ResourceType::Energy, ResourceType::Mineral] {
+        world.spawn((
+            GameResource::new(kind),
+            Collider {
+                bounding_box: Aabb2d::new(position.truncate(), Vec2::splat(5.0)),
+            },
+            Transform::from_translation(position),
+        ));
+    }
+
+    app.update();
+    let world = app.world();
+    let charge = world.get::<Battery>(collector).unwrap().charge;
+    assert!(charge > 74.0 && charge <= 75.0, "{charge}");
+    let cargo: Vec<ResourceType> = world
+        .get::<Cargo>(collector)
+        .unwrap()
+        .items
+        .iter()
+        .map(|item| item.kind)
+        .collect();
+    assert_eq!(cargo, [ResourceType::Mineral]);
+    assert_eq!(world.resource::<Score>().0, 0);
+}
+
+#[test]
+fn disabled_battery_is_not_attached() {
+    let mut app = app(BatteryConfig {
+        enabled: false,
+        ..Default::default()
+    });
+    app.update();
+
+    let world = app.world_mut();
+    assert_eq!(
+        world
+            .query_filtered::<(), With<Battery>>()
+            .iter(world)
+            .count(),
+        0
+    );
+    assert!(
+        world
+            .query_filtered::<(), With<Robot>>()
+            .iter(world)
+            .count()
+            > 0
+    );
+}
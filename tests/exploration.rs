@@ -0,0 +1,93 @@
+mod common;
+
+use bevy::prelude::*;
+use essaim_robots::config::SimulationConfig;
+use essaim_robots::exploration::{CellsExplored, Coverage};
+use essaim_robots::map::Map;
+use essaim_robots::robot::{Robot, Sensor};
+
+// Cases reçues dans les CellsExplored
+#[derive(Resource, Default)]
+struct Revealed(Vec<UVec2>);
+
+fn count_revealed(mut events: EventReader<CellsExplored>, mut revealed: ResMut<Revealed>) {
+    for event in events.read() {
+        revealed.0.extend(&event.0);
+    }
+}
+
+// Robots immobilisés après leur apparition, à la base
+fn frozen_simulation() -> App {
+    let mut app = common::simulation(SimulationConfig::default());
+    app.init_resource::<Revealed>()
+        .add_systems(PostUpdate, count_revealed);
+    common::start(&mut app);
+    app.update();
+
+    let world = app.world_mut();
+    for mut robot in world.query::<&mut Robot>().iter_mut(world) {
+        robot.speed = 0.0;
+    }
+    for _ in 0..3 {
+        app.update();
+    }
+    app
+}
+
+#[test]
+fn sensors_reveal_their_footprint_once() {
+    let mut app = frozen_simulation();
+
+    let world = app.world_mut();
+    let mut maps = world.query::<&Map>();
+    let mut robots = world.query_filtered::<(&Transform, &Children), With<Robot>>();
+    let world = app.world();
+    let map = maps.single(world);
+    let mut footprint: Vec<UVec2> = robots
+        .iter(world)
+        .filter_map(|(transform, children)| {
+            let sensor = children
+                .iter()
+                .find_map(|&child| world.get::<Sensor>(child))?;
+            Some(sensor.footprint(map, transform.translation.truncate()))
+        })
+        .flatten()
+        .collect();
+    footprint.sort_by_key(|cell| (cell.x, cell.y));
+    footprint.dedup();
+
+    assert!(!footprint.is_empty());
+    assert!(footprint.iter().all(|&cell| map.is_explored(cell)));
+
+    // Chaque case révélée n'est annoncée qu'une fois
+    let mut revealed = world.resource::<Revealed>().0.clone();
+    let announced = revealed.len();
+    revealed.sort_by_key(|cell| (cell.x, cell.y));
+    revealed.dedup();
+    assert_eq!(revealed.len(), announced);
+    assert!(footprint.iter().all(|cell| revealed.contains(cell)));
+
+    let coverage = world.resource::<Coverage>().0;
+    assert!((coverage - map.coverage() * 100.0).abs() < 1e-4);
+    assert!(coverage > 0.0);
+}
+
+#[test]
+fn the_map_is_unchanged_when_nothing_new_is_seen() {
+    let mut app = frozen_simulation();
+
+    let world = app.world_mut();
+    let changed = world.query::<Ref<Map>>().single(world).last_changed();
+    let revealed = world.resource::<Revealed>().0.len();
+
+    for _ in 0..5 {
+        app.update();
+    }
+
+    let world = app.world_mut();
+    assert_eq!(
+        world.query::<Ref<Map>>().single(world).last_changed(),
+        changed
+    );
+    assert_eq!(world.resource::<Revealed>().0.len(), revealed);
+}
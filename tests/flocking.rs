@@ -0,0 +1,105 @@
+mod common;
+
+use bevy::prelude::*;
+use essaim_robots::common::DiscoveredResources;
+use essaim_robots::config::{FlockingConfig, SimulationConfig};
+use essaim_robots::flocking::{mean_distance_to_center, polarisation, Flocking, SwarmMetrics};
+use essaim_robots::map::Map;
+use essaim_robots::robot::Robot;
+use essaim_robots::state::RobotState;
+use essaim_robots::steering::DEFAULT_STEERING;
+use essaim_robots::strategy::{Neighbour, Observation, RobotBehavior};
+use rand::rngs::mock::StepRng;
+
+#[test]
+fn metrics_measure_alignment_and_spread() {
+    assert_eq!(polarisation(&[]), 0.0);
+    assert!((polarisation(&[0.5, 0.5, 0.5]) - 1.0).abs() < 1e-6);
+    assert!(polarisation(&[0.0, std::f32::consts::PI]) < 1e-6);
+
+    assert_eq!(mean_distance_to_center(&[Vec2::ONE]), 0.0);
+    assert_eq!(
+        mean_distance_to_center(&[Vec2::new(-3.0, 0.0), Vec2::new(3.0, 0.0)]),
+        3.0
+    );
+}
+
+#[test]
+fn flocking_turns_with_its_neighbours() {
+    let robot = Robot {
+        direction: 0.0,
+        radius: 5.0,
+        speed: 10.0,
+        throttle: 1.0,
+        max_turn_rate: 1.0,
+        max_deviation: 1.0,
+    };
+    let config = SimulationConfig::default();
+    let flocking = FlockingConfig {
+        separation: 0.0,
+        cohesion: 0.0,
+        ..Default::default()
+    };
+    let known = DiscoveredResources(vec![]);
+    // Voisins en route vers le nord
+    let neighbours: Vec<Neighbour> = (0..3)
+        .map(|i| Neighbour {
+            entity: Entity::from_raw(i),
+            position: Vec2::new(i as f32 * 10.0, 20.0),
+            velocity: Vec2::new(0.0, 10.0),
+        })
+        .collect();
+
+    let decision = Flocking.decide(
+        &Observation {
+            robot: &robot,
+            position: Vec2::ZERO,
+            state: RobotState::Wandering,
+            sensor: None,
+            known: &known,
+            config: &config.explorers,
+            flocking: &flocking,
+            map: &Map::new(16, 16, 16),
+            steering: DEFAULT_STEERING,
+            neighbours: &neighbours,
+            delta: 0.1,
+        },
+        &mut StepRng::new(0, 1),
+    );
+
+    // Rotation limitée par la vitesse de rotation du robot
+    assert!((decision.heading.unwrap() - 0.1).abs() < 1e-6);
+    assert_eq!(decision.speed, Some(1.0));
+}
+
+fn run(strategy: &str) -> (f32, f32) {
+    let mut config = SimulationConfig::default();
+    config.explorers.count = 10;
+    config.explorers.strategy = Some(strategy.to_string());
+    config.collectors.count = 0;
+
+    let mut app = common::simulation(config);
+    common::start(&mut app);
+
+    for _ in 0..1200 {
+        app.update();
+    }
+
+    let metrics = app.world().resource::<SwarmMetrics>();
+    (metrics.mean_polarisation, metrics.mean_cohesion)
+}
+
+#[test]
+fn flocking_robots_move_as_a_group() {
+    let (flock_polarisation, flock_cohesion) = run("Flocking");
+    let (walk_polarisation, walk_cohesion) = run("RandomWalk");
+
+    assert!(
+        flock_polarisation > walk_polarisation,
+        "{flock_polarisation} <= {walk_polarisation}"
+    );
+    assert!(
+        flock_cohesion < walk_cohesion,
+        "{flock_cohesion} >= {walk_cohesion}"
+    );
+}
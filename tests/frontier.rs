@@ -0,0 +1,116 @@
+mod common;
+
+use bevy::math::uvec2;
+use bevy::prelude::*;
+use essaim_robots::config::{ExplorationStrategy, SimulationConfig};
+use essaim_robots::exploration::Coverage;
+use essaim_robots::frontier::{frontier_clusters, is_frontier, FrontierTarget};
+use essaim_robots::map::Map;
+use essaim_robots::robot::Explorer;
+
+// Moitié gauche explorée
+fn explored(cell: UVec2) -> bool {
+    cell.x <= 2
+}
+
+#[test]
+fn frontier_is_the_edge_of_explored_space() {
+    let map = Map::new(6, 3, 16);
+
+    assert!(is_frontier(&map, uvec2(2, 1), &explored));
+    assert!(!is_frontier(&map, uvec2(1, 1), &explored));
+    assert!(!is_frontier(&map, uvec2(3, 1), &explored));
+}
+
+#[test]
+fn neighbouring_frontier_cells_form_one_cluster() {
+    let map = Map::new(6, 3, 16);
+
+    let clusters = frontier_clusters(&map, &explored, 1);
+    assert_eq!(clusters.len(), 1);
+    assert_eq!(clusters[0].cells.len(), 3);
+    assert_eq!(clusters[0].target, uvec2(2, 1));
+
+    // Un groupe trop petit est ignoré
+    assert!(frontier_clusters(&map, &explored, 4).is_empty());
+}
+
+#[test]
+fn obstacles_split_frontier_clusters() {
+    let mut map = Map::new(6, 3, 16);
+    map.set_obstacle(uvec2(2, 1), true);
+
+    let clusters = frontier_clusters(&map, &explored, 1);
+    assert_eq!(clusters.len(), 2);
+    assert!(clusters.iter().all(|cluster| cluster.cells.len() == 1));
+}
+
+fn exploration(strategy: ExplorationStrategy, seed: u32) -> App {
+    let mut config = SimulationConfig {
+        seed,
+        ..Default::default()
+    };
+    config.exploration.strategy = strategy;
+    // Petite carte, que les frontières couvrent en quelques milliers de pas
+    config.map.width = 100;
+    config.map.height = 100;
+    config.explorers.count = 3;
+    config.collectors.count = 0;
+    let mut app = common::simulation(config);
+    common::start(&mut app);
+    app
+}
+
+#[test]
+fn explorers_head_for_distinct_frontiers() {
+    let mut app = exploration(ExplorationStrategy::Frontier, 0);
+    let spread = app
+        .world()
+        .resource::<SimulationConfig>()
+        .exploration
+        .spread;
+
+    // Les objectifs sont relevés à chaque pas : deux explorateurs ne visent jamais des
+    // frontières plus proches que l'écart configuré
+    let mut assigned = 0;
+    for _ in 0..600 {
+        app.update();
+
+        let world = app.world_mut();
+        let targets: Vec<(UVec2, Vec2)> = world
+            .query_filtered::<&FrontierTarget, With<Explorer>>()
+            .iter(world)
+            .map(|target| (target.cell, target.goal))
+            .collect();
+        for (index, (cell, goal)) in targets.iter().enumerate() {
+            for (other_cell, other_goal) in &targets[index + 1..] {
+                assert_ne!(cell, other_cell);
+                assert!(goal.distance(*other_goal) >= spread);
+            }
+        }
+        assigned = assigned.max(targets.len());
+    }
+
+    assert_eq!(assigned, 3);
+}
+
+#[test]
+fn frontier_exploration_covers_more_than_a_random_walk() {
+    // Graines dont la base n'est pas enclavée par la roche
+    for seed in [0, 2, 7] {
+        let coverage = |strategy| {
+            let mut app = exploration(strategy, seed);
+            for _ in 0..2000 {
+                app.update();
+            }
+            app.world().resource::<Coverage>().0
+        };
+        let frontier = coverage(ExplorationStrategy::Frontier);
+        let random_walk = coverage(ExplorationStrategy::RandomWalk);
+
+        assert!(
+            frontier > random_walk,
+            "graine {seed} : {frontier} % contre {random_walk} %"
+        );
+    }
+}
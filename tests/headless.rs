@@ -0,0 +1,54 @@
+mod common;
+
+use bevy::prelude::*;
+use essaim_robots::config::SimulationConfig;
+use essaim_robots::headless::HeadlessPlugin;
+use essaim_robots::robot::Robot;
+
+const FRAMES: u32 = 100;
+
+// Pas de FixedUpdate exécutés
+#[derive(Resource, Default)]
+struct Ticks(u32);
+
+fn count_ticks(mut ticks: ResMut<Ticks>) {
+    ticks.0 += 1;
+}
+
+fn positions(app: &mut App) -> Vec<Vec3> {
+    let world = app.world_mut();
+    world
+        .query_filtered::<&Transform, With<Robot>>()
+        .iter(world)
+        .map(|transform| transform.translation)
+        .collect()
+}
+
+#[test]
+fn each_headless_frame_advances_the_simulation_by_one_fixed_step() {
+    let mut app = common::simulation(SimulationConfig::default());
+    assert!(app.is_plugin_added::<HeadlessPlugin>());
+    app.init_resource::<Ticks>()
+        .add_systems(FixedUpdate, count_ticks);
+    common::start(&mut app);
+
+    app.update();
+    let start = positions(&mut app);
+    let ticks = app.world().resource::<Ticks>().0;
+
+    for _ in 0..FRAMES {
+        app.update();
+    }
+
+    let world = app.world();
+    assert_eq!(world.resource::<Ticks>().0 - ticks, FRAMES);
+    let timestep = world.resource::<Time<Fixed>>().timestep();
+    assert_eq!(
+        world.resource::<Time<Fixed>>().elapsed(),
+        timestep * (ticks + FRAMES)
+    );
+
+    let end = positions(&mut app);
+    let travelled: f32 = start.iter().zip(&end).map(|(a, b)| a.distance(*b)).sum();
+    assert!(travelled > 0.0, "les robots n'ont pas bougé");
+}
@@ -0,0 +1,187 @@
+mod common;
+
+use bevy::prelude::*;
+use bevy::time::TimeUpdateStrategy;
+use essaim_robots::common::{Base, CommonPlugin, Statistics};
+use essaim_robots::comms::CommsPlugin;
+use essaim_robots::config::{CommsConfig, KnowledgeModel, SimulationConfig};
+use essaim_robots::knowledge::{Knowledge, KnowledgePlugin};
+use essaim_robots::map::Map;
+use essaim_robots::robot::Robot;
+use essaim_robots::spatial::SpatialPlugin;
+
+fn robot(index: u32) -> Option<Entity> {
+    Some(Entity::from_raw(index))
+}
+
+fn resource(index: u32) -> Entity {
+    Entity::from_raw(100 + index)
+}
+
+#[test]
+fn merge_shares_resources_cells_and_collected() {
+    let mut first = Knowledge::new(robot(0), 10, 10);
+    let mut second = Knowledge::new(robot(1), 10, 10);
+
+    first.learn(resource(0), Vec2::new(10.0, 10.0));
+    first.learn(resource(1), Vec2::new(50.0, 50.0));
+    first.explored.insert(UVec2::new(1, 1));
+    second.learn(resource(2), Vec2::new(30.0, 30.0));
+    second.explored.insert(UVec2::new(9, 9));
+    second.forget(resource(1));
+
+    first.merge(&second);
+
+    assert!(first.knows(resource(0)));
+    assert!(first.knows(resource(2)));
+    // Une ressource collectée n'est pas réapprise
+    assert!(!first.knows(resource(1)));
+    assert!(!first.learn(resource(1), Vec2::new(50.0, 50.0)));
+    assert_eq!(first.explored.len(), 2);
+    assert!(first.explored.contains(UVec2::new(9, 9)));
+}
+
+#[test]
+fn resources_are_identified_by_entity() {
+    let mut knowledge = Knowledge::new(robot(0), 10, 10);
+    let position = Vec2::new(10.0, 10.0);
+
+    // Deux ressources au même endroit restent distinctes
+    assert!(knowledge.learn(resource(0), position));
+    assert!(knowledge.learn(resource(1), position));
+    assert!(!knowledge.learn(resource(0), position));
+
+    knowledge.forget(resource(0));
+    assert!(!knowledge.knows(resource(0)));
+    assert!(knowledge.knows(resource(1)));
+}
+
+#[test]
+fn each_robot_owns_its_claims() {
+    let position = Vec2::new(10.0, 10.0);
+    let mut first = Knowledge::new(robot(0), 10, 10);
+    let mut second = Knowledge::new(robot(1), 10, 10);
+    first.learn(resource(0), position);
+    second.learn(resource(0), position);
+
+    // Le second robot apprend la réservation du premier
+    first.resources.0[0].claimed_by = robot(0);
+    second.merge(&first);
+    assert_eq!(second.resources.0[0].claimed_by, robot(0));
+
+    // Le premier y renonce : le second l'apprend à la fusion suivante
+    first.resources.0[0].claimed_by = None;
+    second.merge(&first);
+    assert_eq!(second.resources.0[0].claimed_by, None);
+
+    // Une réservation attribuée au robot lui-même par un autre n'est pas reprise
+    let mut base = Knowledge::new(None, 10, 10);
+    base.learn(resource(0), position);
+    base.resources.0[0].claimed_by = robot(0);
+    first.merge(&base);
+    assert_eq!(first.resources.0[0].claimed_by, None);
+}
+
+#[test]
+fn local_model_gives_robots_and_base_their_own_knowledge() {
+    let mut app = common::simulation(SimulationConfig {
+        knowledge: KnowledgeModel::Local,
+        ..Default::default()
+    });
+    common::start(&mut app);
+
+    for _ in 0..10 {
+        app.update();
+    }
+
+    let world = app.world_mut();
+    let robots: Vec<(Entity, usize)> = world
+        .query_filtered::<(Entity, &Knowledge), With<Robot>>()
+        .iter(world)
+        .map(|(entity, knowledge)| {
+            assert_eq!(knowledge.owner, Some(entity));
+            (entity, knowledge.explored.len())
+        })
+        .collect();
+    assert_eq!(robots.len(), 4);
+    assert!(robots.iter().all(|&(_, explored)| explored > 0));
+
+    // Les robots partent de la base : elle a déjà reçu ce qu'ils ont vu
+    let base = world
+        .query_filtered::<&Knowledge, With<Base>>()
+        .single(world);
+    assert_eq!(base.owner, None);
+    assert!(!base.explored.is_empty());
+}
+
+fn spawn_robot(app: &mut App, x: f32, knowledge: Knowledge) -> Entity {
+    app.world_mut()
+        .spawn((
+            Transform::from_xyz(x, 0.0, 1.0),
+            Robot {
+                direction: 0.0,
+                radius: 5.0,
+                speed: 0.0,
+                throttle: 1.0,
+                max_turn_rate: 1.0,
+                max_deviation: 1.0,
+            },
+            knowledge,
+        ))
+        .id()
+}
+
+#[test]
+fn rendezvous_shares_knowledge_as_it_was_when_sent() {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        CommonPlugin,
+        SpatialPlugin,
+        CommsPlugin,
+        KnowledgePlugin,
+    ))
+    .insert_resource(SimulationConfig {
+        knowledge: KnowledgeModel::Local,
+        comms: CommsConfig {
+            range: 100.0,
+            latency: 0.5,
+            ..Default::default()
+        },
+        ..Default::default()
+    })
+    .insert_resource(TimeUpdateStrategy::ManualDuration(
+        Time::<Fixed>::default().timestep(),
+    ));
+
+    app.world_mut().spawn(Map::new(10, 10, 16));
+
+    let mut sender_knowledge = Knowledge::new(robot(0), 10, 10);
+    sender_knowledge.learn(resource(0), Vec2::ZERO);
+    let sender = spawn_robot(&mut app, 0.0, sender_knowledge);
+    let receiver = spawn_robot(&mut app, 50.0, Knowledge::new(robot(1), 10, 10));
+
+    // L'émetteur apprend une autre ressource juste après sa première annonce
+    while app.world().resource::<Statistics>().messages_sent == 0 {
+        app.update();
+    }
+    app.world_mut()
+        .get_mut::<Knowledge>(sender)
+        .unwrap()
+        .learn(resource(1), Vec2::ONE);
+
+    // La première annonce arrive avec ce qu'il savait en l'envoyant
+    while !app
+        .world()
+        .get::<Knowledge>(receiver)
+        .unwrap()
+        .knows(resource(0))
+    {
+        app.update();
+    }
+    assert!(!app
+        .world()
+        .get::<Knowledge>(receiver)
+        .unwrap()
+        .knows(resource(1)));
+}
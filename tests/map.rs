@@ -0,0 +1,281 @@
+use bevy::math::{uvec2, vec2, Vec2};
+use essaim_robots::map::{Map, TerrainKind, TerrainThreshold};
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+fn striped_map() -> Map {
+    let mut map = Map::new(4, 1, 16);
+    map.noise_map = vec![0.0, 0.3, 0.5, 0.9];
+    map.classify(&[
+        TerrainThreshold {
+            kind: TerrainKind::Highland,
+            above: 0.75,
+        },
+        TerrainThreshold {
+            kind: TerrainKind::Rock,
+            above: 0.2,
+        },
+        TerrainThreshold {
+            kind: TerrainKind::Sand,
+            above: 0.35,
+        },
+    ]);
+    map
+}
+
+#[test]
+fn highest_exceeded_threshold_wins() {
+    let map = striped_map();
+
+    assert_eq!(
+        map.terrain,
+        vec![
+            TerrainKind::Plain,
+            TerrainKind::Rock,
+            TerrainKind::Sand,
+            TerrainKind::Highland
+        ]
+    );
+}
+
+#[test]
+fn world_positions_map_to_centered_cells() {
+    let map = striped_map();
+
+    assert_eq!(map.cell_at(vec2(-7.9, 0.0)), Some(uvec2(0, 0)));
+    assert_eq!(map.cell_at(vec2(8.1, 0.0)), Some(uvec2(1, 0)));
+    assert_eq!(map.cell_at(vec2(-8.1, 0.0)), None);
+    assert_eq!(map.cell_at(vec2(0.0, 8.1)), None);
+    assert_eq!(map.terrain_at(vec2(33.0, 2.0)), Some(TerrainKind::Sand));
+}
+
+#[test]
+fn only_rock_and_outside_are_not_walkable() {
+    let map = striped_map();
+
+    assert!(map.is_walkable(uvec2(0, 0)));
+    assert!(!map.is_walkable(uvec2(1, 0)));
+    assert!(map.is_walkable(uvec2(3, 0)));
+    assert!(!map.is_walkable(uvec2(4, 0)));
+}
+
+fn walled_map() -> Map {
+    // Une colonne de roche en x = 5 sur une carte 10 x 10
+    let mut map = Map::new(10, 10, 16);
+    for y in 0..10 {
+        map.set_obstacle(uvec2(5, y), true);
+    }
+    map
+}
+
+#[test]
+fn circle_touching_a_blocked_cell_hits() {
+    let map = walled_map();
+
+    assert!(map.circle_hits_obstacle(vec2(60.0, 40.0), 13.0));
+    assert!(!map.circle_hits_obstacle(vec2(60.0, 40.0), 11.0));
+    assert!(!map.circle_hits_obstacle(vec2(0.0, 0.0), 20.0));
+}
+
+#[test]
+fn fast_sweep_cannot_tunnel_through_a_wall() {
+    let map = walled_map();
+
+    // Les deux extrémités sont libres mais le trajet traverse le mur
+    assert!(!map.circle_hits_obstacle(vec2(20.0, 40.0), 5.0));
+    assert!(!map.circle_hits_obstacle(vec2(140.0, 40.0), 5.0));
+    let t = map
+        .sweep_circle(vec2(20.0, 40.0), vec2(140.0, 40.0), 5.0)
+        .unwrap();
+    assert!(t > 0.0 && t < 0.5);
+
+    assert_eq!(
+        map.sweep_circle(vec2(20.0, 40.0), vec2(20.0, 140.0), 5.0),
+        None
+    );
+}
+
+#[test]
+fn sweep_returns_the_exact_contact() {
+    let map = walled_map();
+
+    // Face du mur en x = 72 : contact quand le centre atteint x = 67
+    let t = map
+        .sweep_circle(vec2(20.0, 40.0), vec2(140.0, 40.0), 5.0)
+        .unwrap();
+    assert!((t - 47.0 / 120.0).abs() < 1e-5, "{t}");
+
+    // Coin d'une case isolée (24..40) abordé en diagonale
+    let mut map = Map::new(10, 10, 16);
+    map.set_obstacle(uvec2(2, 2), true);
+    let t = map
+        .sweep_circle(vec2(0.0, 0.0), vec2(60.0, 60.0), 5.0)
+        .unwrap();
+    let contact = 24.0 - 5.0 / 2.0_f32.sqrt();
+    assert!((t - contact / 60.0).abs() < 1e-5, "{t}");
+
+    // Le long du bord supérieur de la case, à un cheveu près
+    assert_eq!(
+        map.sweep_circle(vec2(0.0, 45.01), vec2(60.0, 45.01), 5.0),
+        None
+    );
+    assert!(map
+        .sweep_circle(vec2(0.0, 44.99), vec2(60.0, 44.99), 5.0)
+        .is_some());
+}
+
+#[test]
+fn sweep_with_a_tiny_radius_still_hits() {
+    let map = walled_map();
+
+    let t = map
+        .sweep_circle(vec2(0.0, 0.0), vec2(150.0, 150.0), 1e-6)
+        .unwrap();
+    assert!((t - 72.0 / 150.0).abs() < 1e-5, "{t}");
+}
+
+#[test]
+fn sweep_agrees_with_dense_sampling() {
+    let mut rng = ChaCha8Rng::seed_from_u64(3);
+    let mut map = Map::new(20, 20, 16);
+    for _ in 0..60 {
+        map.set_obstacle(uvec2(rng.gen_range(0..20), rng.gen_range(0..20)), true);
+    }
+
+    for _ in 0..500 {
+        let start = vec2(rng.gen_range(-20.0..340.0), rng.gen_range(-20.0..340.0));
+        let end = vec2(rng.gen_range(-20.0..340.0), rng.gen_range(-20.0..340.0));
+        let radius = rng.gen_range(0.01..20.0);
+        let sampled = (0..=2000)
+            .map(|i| i as f32 / 2000.0)
+            .find(|t| map.circle_hits_obstacle(start.lerp(end, *t), radius));
+
+        match (map.sweep_circle(start, end, radius), sampled) {
+            (None, None) => {}
+            (Some(t), Some(sampled)) => assert!(t <= sampled + 1e-4, "{t} > {sampled}"),
+            // Un contact plus court que l'écart entre deux échantillons
+            (Some(t), None) => {
+                assert!(!map.circle_hits_obstacle(start.lerp(end, t), radius * 0.99))
+            }
+            (None, Some(sampled)) => panic!("missed a contact at {sampled}"),
+        }
+    }
+}
+
+#[test]
+fn circle_slides_along_a_wall_instead_of_stopping() {
+    let map = walled_map();
+
+    // En diagonale vers le mur : bloqué en x, le déplacement en y est conservé
+    let reached = map.slide_circle(vec2(60.0, 40.0), vec2(30.0, 30.0), 5.0);
+    assert!(reached.x > 66.5 && reached.x <= 67.0, "{reached}");
+    assert!((reached.y - 70.0).abs() < 1e-4, "{reached}");
+    assert!(!map.circle_hits_obstacle(reached, 5.0));
+
+    // Droit dans le mur : il n'avance que jusqu'au contact
+    let reached = map.slide_circle(vec2(60.0, 40.0), vec2(200.0, 0.0), 5.0);
+    assert!(reached.x > 66.5 && reached.x <= 67.0, "{reached}");
+    assert_eq!(reached.y, 40.0);
+
+    // Trajet libre
+    assert_eq!(
+        map.slide_circle(vec2(20.0, 40.0), vec2(0.0, 50.0), 5.0),
+        vec2(20.0, 90.0)
+    );
+}
+
+#[test]
+fn overlapping_circle_can_move_away_from_the_obstacle() {
+    let map = walled_map();
+
+    // Le cercle touche déjà le mur : seul son centre est contraint
+    let start = vec2(70.0, 40.0);
+    assert!(map.circle_hits_obstacle(start, 5.0));
+    let reached = map.slide_circle(start, vec2(-20.0, 0.0), 5.0);
+    assert_eq!(reached, vec2(50.0, 40.0));
+
+    let reached = map.slide_circle(start, vec2(20.0, 0.0), 5.0);
+    assert!(reached.x < 72.0 && !map.is_blocked_at(reached), "{reached}");
+}
+
+#[test]
+fn a_diagonal_move_cannot_cut_the_corner_of_a_blocked_cell() {
+    // Deux cases bloquées qui se touchent par un coin
+    let mut map = Map::new(10, 10, 16);
+    map.set_obstacle(uvec2(5, 5), true);
+    map.set_obstacle(uvec2(6, 6), true);
+
+    // Le trajet entame le coin de (5, 5) sur moins d'un demi-pixel : le centre s'arrête
+    // au contact et ne peut plus glisser entre les deux cases
+    let reached = map.slide_circle(vec2(80.0, 95.6), vec2(15.0, -15.0), 0.0);
+    assert!(reached.x < 88.0 && reached.y >= 88.0, "{reached}");
+    assert!(!map.is_blocked_at(reached), "{reached}");
+}
+
+#[test]
+fn a_tiny_radius_slides_exactly_like_a_point() {
+    let map = walled_map();
+
+    let reached = map.slide_circle(vec2(0.0, 10.0), vec2(150.0, 30.0), 1e-6);
+    assert!(reached.x < 72.0 && reached.x > 71.9, "{reached}");
+    assert!((reached.y - 40.0).abs() < 1e-4, "{reached}");
+}
+
+#[test]
+fn fuzzed_speeds_and_deltas_never_enter_a_blocked_cell() {
+    let mut rng = ChaCha8Rng::seed_from_u64(25);
+    let mut map = Map::new(20, 20, 16);
+    for y in 0..20 {
+        for x in 0..20 {
+            if rng.gen_bool(0.3) {
+                map.set_obstacle(uvec2(x, y), true);
+            }
+        }
+    }
+    let extent = map.extent();
+    let inside = |position: Vec2, margin: f32| {
+        position.cmpge(Vec2::splat(margin)).all() && position.cmple(extent - margin).all()
+    };
+
+    for _ in 0..200 {
+        let radius = rng.gen_range(1.0..12.0);
+        let mut position = vec2(rng.gen_range(0.0..extent.x), rng.gen_range(0.0..extent.y));
+        if map.is_blocked_at(position) {
+            continue;
+        }
+        // Un cercle qui chevauche déjà un obstacle ou un bord ne doit pas y faire entrer son
+        // centre ; sinon, il ne doit plus en toucher
+        let free = inside(position, radius) && !map.circle_hits_obstacle(position, radius);
+
+        for _ in 0..50 {
+            let speed = rng.gen_range(0.0..2000.0);
+            let delta = rng.gen_range(0.001..0.5);
+            let direction = rng.gen_range(0.0..std::f32::consts::TAU);
+            let motion = Vec2::from_angle(direction) * speed * delta;
+
+            position = map.slide_circle(position, motion, radius);
+            assert!(inside(position, 0.0), "{position} radius {radius}");
+            assert!(!map.is_blocked_at(position), "{position} radius {radius}");
+            if free {
+                assert!(inside(position, radius), "{position} radius {radius}");
+                assert!(!map.circle_hits_obstacle(position, radius), "{position}");
+            }
+        }
+    }
+}
+
+#[test]
+fn sensor_footprint_marks_cells_explored_once() {
+    let mut map = Map::new(10, 10, 16);
+    let footprint: Vec<_> = map.cells_within(vec2(0.0, 0.0), 16.0).collect();
+
+    // La case d'origine et ses deux voisines dans la carte
+    assert_eq!(footprint.len(), 3);
+    for cell in footprint {
+        assert!(map.mark_explored(cell));
+    }
+    assert!(!map.mark_explored(uvec2(0, 0)));
+    assert!(map.is_explored(uvec2(1, 0)));
+    assert!(!map.is_explored(uvec2(1, 1)));
+    assert_eq!(map.coverage(), 0.03);
+}
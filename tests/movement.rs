@@ -0,0 +1,64 @@
+mod common;
+
+use bevy::prelude::*;
+use bevy::time::TimeUpdateStrategy;
+use essaim_robots::config::SimulationConfig;
+use essaim_robots::map::Map;
+use essaim_robots::robot::Robot;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use std::time::Duration;
+
+// Vitesses et pas de temps tirés au hasard à chaque pas : aucun robot n'entre dans une case
+// bloquée ni ne sort de la carte, même par une brèche dans des bords de roche
+#[test]
+fn fuzzed_speeds_and_deltas_keep_robots_on_free_cells() {
+    let mut config = SimulationConfig::default();
+    config.battery.enabled = false;
+    config.explorers.count = 4;
+    config.collectors.count = 4;
+    let mut app = common::simulation(config);
+    common::start(&mut app);
+    app.update();
+
+    // Bords de roche percés d'une case sur trois, par où un robot pourrait sortir
+    let world = app.world_mut();
+    let mut map = world.query::<&mut Map>().single_mut(world);
+    let (width, height) = (map.width, map.height);
+    let border = (0..width)
+        .flat_map(|x| [UVec2::new(x, 0), UVec2::new(x, height - 1)])
+        .chain((0..height).flat_map(|y| [UVec2::new(0, y), UVec2::new(width - 1, y)]));
+    for cell in border.collect::<Vec<_>>() {
+        map.set_obstacle(cell, (cell.x + cell.y) % 3 != 0);
+    }
+
+    let mut rng = ChaCha8Rng::seed_from_u64(25);
+    for _ in 0..300 {
+        let delta = Duration::from_secs_f32(rng.gen_range(0.001..0.5));
+        let world = app.world_mut();
+        world.insert_resource(TimeUpdateStrategy::ManualDuration(delta));
+        world.resource_mut::<Time<Fixed>>().set_timestep(delta);
+        for mut robot in world.query::<&mut Robot>().iter_mut(world) {
+            robot.speed = rng.gen_range(0.0..2000.0);
+        }
+        app.update();
+
+        let world = app.world_mut();
+        let robots: Vec<Vec2> = world
+            .query_filtered::<&Transform, With<Robot>>()
+            .iter(world)
+            .map(|transform| transform.translation.truncate())
+            .collect();
+        let map = world.query::<&Map>().single(world);
+        for position in robots {
+            assert!(
+                position.cmpge(Vec2::ZERO).all() && position.cmple(map.extent()).all(),
+                "{position} is off the map"
+            );
+            assert!(
+                !map.is_blocked_at(position),
+                "{position} is in a blocked cell"
+            );
+        }
+    }
+}
@@ -0,0 +1,115 @@
+use bevy::math::{uvec2, vec2, Vec2};
+use essaim_robots::map::{Map, TerrainKind};
+use essaim_robots::pathfinding::{find_cell_path, PathCache};
+
+// Mur vertical en x = 5 avec une seule ouverture en y = 8
+fn walled_map(gap: bool) -> Map {
+    let mut map = Map::new(10, 10, 16);
+    for y in 0..10 {
+        if !(gap && y == 8) {
+            map.set_obstacle(uvec2(5, y), true);
+        }
+    }
+    map
+}
+
+#[test]
+fn path_goes_through_the_gap() {
+    let map = walled_map(true);
+    let cells = find_cell_path(&map, uvec2(1, 1), uvec2(8, 1), 0.0, false).unwrap();
+
+    assert_eq!(cells.first(), Some(&vec2(16.0, 16.0)));
+    assert_eq!(cells.last(), Some(&vec2(128.0, 16.0)));
+    assert!(cells.contains(&map.cell_center(uvec2(5, 8))));
+    assert!(cells
+        .iter()
+        .all(|pos| map.is_walkable(map.cell_at(*pos).unwrap())));
+}
+
+#[test]
+fn closed_wall_is_unreachable() {
+    let map = walled_map(false);
+
+    assert_eq!(
+        find_cell_path(&map, uvec2(1, 1), uvec2(8, 1), 0.0, false),
+        None
+    );
+}
+
+#[test]
+fn clearance_rejects_gaps_narrower_than_the_robot() {
+    let map = walled_map(true);
+
+    // Une ouverture d'une case (16) ne laisse pas passer un robot de rayon 10
+    assert_eq!(
+        find_cell_path(&map, uvec2(1, 1), uvec2(8, 1), 10.0, false),
+        None
+    );
+    assert!(find_cell_path(&map, uvec2(1, 1), uvec2(8, 1), 5.0, false).is_some());
+}
+
+#[test]
+fn terrain_cost_avoids_expensive_cells() {
+    let mut map = Map::new(5, 3, 16);
+    for x in 1..4 {
+        map.terrain[(map.width + x) as usize] = TerrainKind::Highland;
+    }
+
+    let direct = find_cell_path(&map, uvec2(0, 1), uvec2(4, 1), 0.0, false).unwrap();
+    let detour = find_cell_path(&map, uvec2(0, 1), uvec2(4, 1), 0.0, true).unwrap();
+
+    assert_eq!(direct.len(), 5);
+    assert!(detour
+        .iter()
+        .all(|pos| map.terrain_at(*pos) != Some(TerrainKind::Highland)));
+}
+
+#[test]
+fn cached_paths_are_smoothed_and_replanned_on_change() {
+    let mut map = walled_map(true);
+    let mut cache = PathCache::default();
+
+    let start = vec2(18.0, 14.0);
+    let goal = vec2(126.0, 18.0);
+    let waypoints = cache.find_path(&map, start, goal, 4.0, false).unwrap();
+    assert_eq!(waypoints.last(), Some(&goal));
+    assert!(waypoints.len() >= 2 && waypoints.len() < 8);
+
+    // Fermer l'ouverture invalide le cache
+    map.set_obstacle(uvec2(5, 8), true);
+    assert_eq!(cache.find_path(&map, start, goal, 4.0, false), None);
+
+    // Sans mur, la ligne droite suffit
+    let open = Map::new(10, 10, 16);
+    assert_eq!(
+        cache.find_path(&open, start, goal, 4.0, false),
+        Some(vec![goal])
+    );
+}
+
+#[test]
+fn cached_paths_depend_on_the_terrain_cost() {
+    // Mur en x = 3 : le détour par le haut, court, traverse une rangée de hauts plateaux
+    let mut map = Map::new(7, 6, 16);
+    for y in 1..5 {
+        map.set_obstacle(uvec2(3, y), true);
+    }
+    for x in 0..7 {
+        map.terrain[x] = TerrainKind::Highland;
+    }
+    let mut cache = PathCache::default();
+
+    let start = map.cell_center(uvec2(1, 1));
+    let goal = map.cell_center(uvec2(5, 1));
+    let heights = |waypoints: Vec<Vec2>| {
+        waypoints
+            .iter()
+            .map(|waypoint| waypoint.y)
+            .collect::<Vec<_>>()
+    };
+
+    let shortest = heights(cache.find_path(&map, start, goal, 0.0, false).unwrap());
+    assert!(shortest.contains(&0.0), "{shortest:?}");
+    let cheapest = heights(cache.find_path(&map, start, goal, 0.0, true).unwrap());
+    assert!(cheapest.contains(&80.0), "{cheapest:?}");
+}
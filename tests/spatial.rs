@@ -0,0 +1,54 @@
+use bevy::prelude::*;
+use essaim_robots::spatial::ResourceIndex;
+
+fn entity(index: u32) -> Entity {
+    Entity::from_raw(index)
+}
+
+#[test]
+fn radius_query_spans_neighbouring_cells() {
+    let mut index = ResourceIndex::new(10.0);
+    index.insert(entity(0), Vec2::new(1.0, 1.0));
+    index.insert(entity(1), Vec2::new(12.0, 1.0));
+    index.insert(entity(2), Vec2::new(-9.0, -9.0));
+    index.insert(entity(3), Vec2::new(40.0, 40.0));
+
+    let mut found: Vec<Entity> = index
+        .within_radius(Vec2::ZERO, 15.0)
+        .map(|(e, _)| e)
+        .collect();
+    found.sort();
+
+    assert_eq!(found, vec![entity(0), entity(1), entity(2)]);
+}
+
+#[test]
+fn moved_and_removed_entities_stay_in_sync() {
+    let mut index = ResourceIndex::new(10.0);
+    index.insert(entity(0), Vec2::new(1.0, 1.0));
+    index.insert(entity(0), Vec2::new(55.0, 55.0));
+    index.insert(entity(1), Vec2::new(2.0, 2.0));
+    index.remove(entity(1));
+
+    assert_eq!(index.len(), 1);
+    assert_eq!(index.within_radius(Vec2::ZERO, 20.0).count(), 0);
+    assert_eq!(index.position(entity(0)), Some(Vec2::new(55.0, 55.0)));
+}
+
+#[test]
+fn nearest_respects_distance_and_filter() {
+    let mut index = ResourceIndex::new(10.0);
+    index.insert(entity(0), Vec2::new(3.0, 0.0));
+    index.insert(entity(1), Vec2::new(8.0, 0.0));
+    index.insert(entity(2), Vec2::new(30.0, 0.0));
+
+    assert_eq!(
+        index.nearest(Vec2::ZERO, 20.0, |_| true),
+        Some((entity(0), Vec2::new(3.0, 0.0)))
+    );
+    assert_eq!(
+        index.nearest(Vec2::ZERO, 20.0, |e| e != entity(0)),
+        Some((entity(1), Vec2::new(8.0, 0.0)))
+    );
+    assert_eq!(index.nearest(Vec2::ZERO, 2.0, |_| true), None);
+}
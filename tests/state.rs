@@ -0,0 +1,216 @@
+mod common;
+
+use bevy::prelude::*;
+use essaim_robots::common::{Base, CommonPlugin, GameResource, ResourceType};
+use essaim_robots::config::SimulationConfig;
+use essaim_robots::robot::{Cargo, Collector, Robot, RobotPlugin};
+use essaim_robots::state::{next_state, RobotState, Situation, StateChanged, StatePlugin};
+use essaim_robots::strategy::{BehaviorRegistry, Decision, Observation, RobotBehavior};
+use rand::RngCore;
+
+#[derive(Resource, Default)]
+struct Transitions(Vec<StateChanged>);
+
+fn record(mut events: EventReader<StateChanged>, mut transitions: ResMut<Transitions>) {
+    transitions.0.extend(events.read().copied());
+}
+
+#[test]
+fn transitions_follow_priorities() {
+    assert_eq!(next_state(&Situation::default()), RobotState::Wandering);
+    assert_eq!(
+        next_state(&Situation {
+            frontier: true,
+            ..Default::default()
+        }),
+        RobotState::Exploring
+    );
+    // Un collecteur chargé continue de chercher, sauf s'il a une ressource en vue
+    assert_eq!(
+        next_state(&Situation {
+            carrying: true,
+            ..Default::default()
+        }),
+        RobotState::Collecting
+    );
+    assert_eq!(
+        next_state(&Situation {
+            carrying: true,
+            sensing: true,
+            ..Default::default()
+        }),
+        RobotState::Approaching
+    );
+    // Rentrer à la base passe avant la ressource en vue
+    assert_eq!(
+        next_state(&Situation {
+            returning: true,
+            sensing: true,
+            ..Default::default()
+        }),
+        RobotState::Returning
+    );
+    assert_eq!(
+        next_state(&Situation {
+            recharging: true,
+            docked: true,
+            ..Default::default()
+        }),
+        RobotState::Charging
+    );
+    assert_eq!(
+        next_state(&Situation {
+            disabled: true,
+            docked: true,
+            stuck: true,
+            ..Default::default()
+        }),
+        RobotState::Disabled
+    );
+}
+
+#[test]
+fn disabled_is_final() {
+    assert!(!RobotState::Disabled.can_become(RobotState::Wandering));
+    assert!(RobotState::Charging.can_become(RobotState::Disabled));
+}
+
+#[test]
+fn transition_events_chain_for_each_robot() {
+    let mut app = common::simulation(SimulationConfig::default());
+    app.init_resource::<Transitions>()
+        .add_systems(PostUpdate, record);
+    common::start(&mut app);
+
+    for _ in 0..1500 {
+        app.update();
+    }
+
+    let world = app.world_mut();
+    let transitions = std::mem::take(&mut world.resource_mut::<Transitions>().0);
+    assert!(transitions
+        .iter()
+        .any(|transition| transition.to == RobotState::Approaching));
+
+    // Chaque transition part de l'état atteint par la précédente
+    let robots: Vec<(Entity, RobotState)> = world
+        .query::<(Entity, &RobotState)>()
+        .iter(world)
+        .map(|(entity, state)| (entity, *state))
+        .collect();
+    for (robot, state) in robots {
+        let mut current = RobotState::Wandering;
+        for transition in transitions
+            .iter()
+            .filter(|transition| transition.robot == robot)
+        {
+            assert_eq!(transition.from, current);
+            assert_ne!(transition.from, transition.to);
+            current = transition.to;
+        }
+        assert_eq!(current, state);
+    }
+}
+
+#[test]
+fn loaded_collectors_without_resource_in_sight_are_collecting() {
+    // Guidés par un arbre qui se contente d'errer, les collecteurs ne rentrent pas décharger
+    let mut config =
+        SimulationConfig::parse("(behaviours: { \"Collector\": Action(Wander) })").unwrap();
+    config.resources.count = 0;
+    let mut app = common::simulation(config);
+    common::start(&mut app);
+
+    // Loin de la base, pour qu'il ne décharge pas aussitôt
+    for _ in 0..200 {
+        app.update();
+    }
+    let world = app.world_mut();
+    let base = world
+        .query_filtered::<&Transform, With<Base>>()
+        .single(world)
+        .translation;
+    let collector = world
+        .query_filtered::<(Entity, &Transform), With<Collector>>()
+        .iter(world)
+        .max_by(|(_, a), (_, b)| {
+            a.translation
+                .distance(base)
+                .total_cmp(&b.translation.distance(base))
+        })
+        .map(|(entity, _)| entity)
+        .unwrap();
+    world
+        .get_mut::<Cargo>(collector)
+        .unwrap()
+        .items
+        .push(GameResource::new(ResourceType::Mineral));
+
+    app.update();
+    assert_eq!(
+        *app.world().get::<RobotState>(collector).unwrap(),
+        RobotState::Collecting
+    );
+}
+
+// S'arrête sur place
+struct Halt;
+
+impl RobotBehavior for Halt {
+    fn decide(&mut self, _: &Observation, _: &mut dyn RngCore) -> Decision {
+        Decision {
+            speed: Some(0.0),
+            ..Default::default()
+        }
+    }
+}
+
+#[test]
+fn robots_stopped_on_purpose_are_not_stuck() {
+    // Explorateurs sans vitesse, collecteurs arrêtés par leur stratégie
+    let mut config = SimulationConfig::default();
+    config.explorers.speed = 0.0;
+    config.collectors.strategy = Some("Halt".to_string());
+    config.resources.count = 0;
+    let mut app = common::simulation(config);
+    app.world_mut()
+        .resource_mut::<BehaviorRegistry>()
+        .register("Halt", || Halt);
+    app.init_resource::<Transitions>()
+        .add_systems(PostUpdate, record);
+    common::start(&mut app);
+
+    app.update();
+    app.update();
+    let world = app.world_mut();
+    let directions: Vec<f32> = world
+        .query::<&Robot>()
+        .iter(world)
+        .map(|robot| robot.direction)
+        .collect();
+
+    // Bien au-delà du délai après lequel un robot qui n'avance pas est bloqué
+    for _ in 0..400 {
+        app.update();
+    }
+
+    let world = app.world_mut();
+    assert!(world
+        .resource::<Transitions>()
+        .0
+        .iter()
+        .all(|transition| transition.to != RobotState::Stuck));
+    let after: Vec<f32> = world
+        .query::<&Robot>()
+        .iter(world)
+        .map(|robot| robot.direction)
+        .collect();
+    assert_eq!(after, directions);
+}
+
+#[test]
+fn robot_plugin_brings_the_state_machine() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, CommonPlugin, RobotPlugin));
+    assert!(app.is_plugin_added::<StatePlugin>());
+}
@@ -0,0 +1,340 @@
+mod common;
+
+use bevy::prelude::*;
+use essaim_robots::common::DiscoveredResources;
+use essaim_robots::config::{ConfigError, FlockingConfig, SimulationConfig};
+use essaim_robots::map::Map;
+use essaim_robots::robot::{Explorer, Robot, Sensor};
+use essaim_robots::state::RobotState;
+use essaim_robots::steering::{
+    alignment, arrive, avoid_obstacles, blend, cohesion, evade, flee, pursue, seek, separation,
+    turn_towards, wander, Agent, Steering, SteeringSet, DEFAULT_STEERING,
+};
+use essaim_robots::strategy::{Neighbour, Observation, RandomWalk, RobotBehavior};
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+// Agent immobile, tourné vers +x
+fn at_rest(position: Vec2) -> Agent {
+    Agent {
+        position,
+        direction: 0.0,
+        velocity: Vec2::ZERO,
+        max_speed: 10.0,
+    }
+}
+
+fn assert_close(actual: Vec2, expected: Vec2) {
+    assert!(
+        actual.distance(expected) < 1e-4,
+        "expected {expected}, got {actual}"
+    );
+}
+
+#[test]
+fn turns_the_short_way_around() {
+    assert_eq!(turn_towards(0.0, 1.0, 0.25), 0.25);
+    assert!((turn_towards(0.0, -1.0, 2.0) + 1.0).abs() < 1e-6);
+    // De 3 à -3 radians, le plus court est de passer par PI
+    assert!(turn_towards(3.0, -3.0, 0.1) > 3.0);
+}
+
+#[test]
+fn seek_flee_and_arrive_point_at_the_target() {
+    let agent = at_rest(Vec2::ZERO);
+    let target = Vec2::new(0.0, 20.0);
+
+    assert_close(seek(&agent, target), Vec2::new(0.0, 10.0));
+    assert_close(flee(&agent, target), Vec2::new(0.0, -10.0));
+    assert_close(arrive(&agent, target, 10.0), Vec2::new(0.0, 10.0));
+    // Ralentit à l'approche, s'arrête sur la cible
+    assert_close(arrive(&agent, target, 40.0), Vec2::new(0.0, 5.0));
+    assert_close(arrive(&agent, Vec2::ZERO, 40.0), Vec2::ZERO);
+
+    // La force corrige la vitesse actuelle
+    let moving = Agent {
+        velocity: Vec2::new(10.0, 0.0),
+        ..agent
+    };
+    assert_close(seek(&moving, target), Vec2::new(-10.0, 10.0));
+}
+
+#[test]
+fn pursue_and_evade_anticipate_the_target() {
+    let agent = at_rest(Vec2::ZERO);
+    // À 10 unités de vitesse, la cible est atteinte en 2 secondes : elle sera en (20, 20)
+    let pursuit = pursue(&agent, Vec2::new(20.0, 0.0), Vec2::new(0.0, 10.0));
+    assert_close(pursuit, Vec2::new(1.0, 1.0).normalize() * 10.0);
+    assert_close(
+        evade(&agent, Vec2::new(20.0, 0.0), Vec2::new(0.0, 10.0)),
+        -pursuit,
+    );
+}
+
+#[test]
+fn separation_pushes_away_from_close_neighbours() {
+    let agent = at_rest(Vec2::ZERO);
+    let neighbours = [Vec2::new(5.0, 0.0), Vec2::new(0.0, 50.0)];
+
+    assert_close(separation(&agent, &neighbours, 10.0), Vec2::new(-10.0, 0.0));
+    assert_eq!(separation(&agent, &neighbours, 1.0), Vec2::ZERO);
+}
+
+#[test]
+fn forces_are_weighted_and_combined() {
+    let agent = at_rest(Vec2::ZERO);
+    let force = blend(&[
+        (1.0, seek(&agent, Vec2::new(0.0, 20.0))),
+        (0.5, separation(&agent, &[Vec2::new(0.0, 5.0)], 10.0)),
+    ]);
+    assert_close(force, Vec2::new(0.0, 5.0));
+
+    // La rotation est limitée, la vitesse bornée
+    let decision = agent.decide(Vec2::new(0.0, 50.0), 0.5);
+    assert_eq!(decision.heading, Some(0.5));
+    assert_eq!(decision.speed, Some(1.0));
+    let decision = agent.decide(Vec2::ZERO, 0.5);
+    assert_eq!(decision.heading, Some(0.0));
+    assert_eq!(decision.speed, Some(0.0));
+}
+
+#[test]
+fn wander_stays_within_its_angle() {
+    let agent = Agent {
+        velocity: Vec2::new(10.0, 0.0),
+        ..at_rest(Vec2::ZERO)
+    };
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+
+    for _ in 0..100 {
+        let desired = agent.velocity + wander(&agent, &mut rng, 0.3);
+        assert!((desired.length() - 10.0).abs() < 1e-4);
+        assert!(desired.to_angle().abs() <= 0.3);
+    }
+}
+
+#[test]
+fn obstacles_ahead_are_avoided_on_a_free_side() {
+    let mut map = Map::new(10, 10, 16);
+    let agent = at_rest(Vec2::new(24.0, 104.0));
+    assert_eq!(avoid_obstacles(&agent, &map, 5.0, 40.0), Vec2::ZERO);
+
+    // Mur devant l'agent, ouvert vers le haut
+    for y in 0..7 {
+        map.set_obstacle(UVec2::new(3, y), true);
+    }
+    let force = avoid_obstacles(&agent, &map, 5.0, 40.0);
+    assert!(force.y > 0.0 && force.x == 0.0, "got {force}");
+}
+
+#[test]
+fn alignment_and_cohesion_follow_the_neighbours() {
+    let agent = at_rest(Vec2::ZERO);
+
+    assert_close(
+        alignment(&agent, &[Vec2::new(0.0, 3.0), Vec2::new(0.0, 30.0)]),
+        Vec2::new(0.0, 10.0),
+    );
+    assert_eq!(alignment(&agent, &[]), Vec2::ZERO);
+    assert_close(
+        cohesion(&agent, &[Vec2::new(10.0, 10.0), Vec2::new(-10.0, 10.0)]),
+        Vec2::new(0.0, 10.0),
+    );
+    assert_eq!(cohesion(&agent, &[]), Vec2::ZERO);
+}
+
+#[test]
+fn random_walk_follows_the_weighted_behaviours_of_the_robot() {
+    let robot = Robot {
+        direction: 0.0,
+        radius: 5.0,
+        speed: 10.0,
+        throttle: 1.0,
+        max_turn_rate: 1.0,
+        max_deviation: 1.0,
+    };
+    let sensor = Sensor {
+        range: 100,
+        detecting: true,
+        nearest: Some(Vec2::new(0.0, 50.0)),
+    };
+    let known = DiscoveredResources(vec![]);
+    let config = SimulationConfig::default();
+    let flocking = FlockingConfig::default();
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+
+    // Sans errance, le robot se tourne vers la ressource, à la vitesse de rotation près
+    let decision = RandomWalk.decide(
+        &Observation {
+            robot: &robot,
+            position: Vec2::ZERO,
+            state: RobotState::Wandering,
+            sensor: Some(&sensor),
+            known: &known,
+            config: &config.explorers,
+            flocking: &flocking,
+            map: &Map::new(16, 16, 16),
+            steering: &[(1.0, Steering::SeekResource), (0.0, Steering::Wander)],
+            neighbours: &[],
+            delta: 0.1,
+        },
+        &mut rng,
+    );
+    assert!((decision.heading.unwrap() - 0.1).abs() < 1e-6);
+}
+
+#[test]
+fn the_default_behaviours_steer_around_obstacles() {
+    let robot = Robot {
+        direction: 0.0,
+        radius: 5.0,
+        speed: 10.0,
+        throttle: 1.0,
+        max_turn_rate: 1.0,
+        max_deviation: 1.0,
+    };
+    let known = DiscoveredResources(vec![]);
+    let config = SimulationConfig::default();
+    let flocking = FlockingConfig::default();
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+
+    // Mur devant le robot, ouvert vers le haut
+    let mut map = Map::new(10, 10, 16);
+    for y in 0..7 {
+        map.set_obstacle(UVec2::new(3, y), true);
+    }
+    let decision = RandomWalk.decide(
+        &Observation {
+            robot: &robot,
+            position: Vec2::new(24.0, 104.0),
+            state: RobotState::Wandering,
+            sensor: None,
+            known: &known,
+            config: &config.explorers,
+            flocking: &flocking,
+            map: &map,
+            steering: DEFAULT_STEERING,
+            neighbours: &[],
+            delta: 0.1,
+        },
+        &mut rng,
+    );
+    assert!((decision.heading.unwrap() - 0.1).abs() < 1e-6);
+}
+
+#[test]
+fn neighbour_behaviours_react_to_the_nearest_neighbour() {
+    let robot = Robot {
+        direction: 0.0,
+        radius: 5.0,
+        speed: 10.0,
+        throttle: 0.0,
+        max_turn_rate: 1.0,
+        max_deviation: 1.0,
+    };
+    let known = DiscoveredResources(vec![]);
+    let config = SimulationConfig::default();
+    let flocking = FlockingConfig::default();
+    let map = Map::new(16, 16, 16);
+    let sensor = Sensor {
+        range: 100,
+        detecting: true,
+        nearest: Some(Vec2::new(5.0, 0.0)),
+    };
+    // Le plus proche, au nord, part vers l'est
+    let neighbours = [
+        Neighbour {
+            entity: Entity::from_raw(1),
+            position: Vec2::new(0.0, 30.0),
+            velocity: Vec2::new(0.0, 10.0),
+        },
+        Neighbour {
+            entity: Entity::from_raw(0),
+            position: Vec2::new(0.0, 10.0),
+            velocity: Vec2::new(10.0, 0.0),
+        },
+    ];
+    let observation = Observation {
+        robot: &robot,
+        position: Vec2::ZERO,
+        state: RobotState::Wandering,
+        sensor: Some(&sensor),
+        known: &known,
+        config: &config.explorers,
+        flocking: &flocking,
+        map: &map,
+        steering: DEFAULT_STEERING,
+        neighbours: &neighbours,
+        delta: 0.1,
+    };
+    let agent = at_rest(Vec2::ZERO);
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let mut force = |steering: Steering| steering.force(&agent, &observation, &mut rng);
+
+    assert_close(force(Steering::FleeNeighbour(20.0)), Vec2::new(0.0, -10.0));
+    assert_eq!(force(Steering::FleeNeighbour(5.0)), Vec2::ZERO);
+    assert_close(
+        force(Steering::PursueNeighbour),
+        Vec2::new(10.0, 10.0).normalize() * 10.0,
+    );
+    assert_close(
+        force(Steering::EvadeNeighbour(20.0)),
+        Vec2::new(-10.0, -10.0).normalize() * 10.0,
+    );
+    assert_close(force(Steering::ArriveResource(10.0)), Vec2::new(5.0, 0.0));
+    assert!(Steering::PursueNeighbour.needs_neighbours());
+    assert!(!Steering::ObstacleAvoidance { look_ahead: 40.0 }.needs_neighbours());
+}
+
+#[test]
+fn roles_get_their_weighted_behaviours_from_the_configuration() {
+    let config = SimulationConfig::parse(
+        "(steering: { \"Explorer\": [(1.0, Wander), (2.0, Separation(30.0))] })",
+    )
+    .unwrap();
+    let mut app = common::simulation(config);
+    common::start(&mut app);
+    app.update();
+    app.update();
+
+    let world = app.world_mut();
+    let robots: Vec<(Option<SteeringSet>, bool)> = world
+        .query_filtered::<(Option<&SteeringSet>, Has<Explorer>), With<Robot>>()
+        .iter(world)
+        .map(|(set, explorer)| (set.cloned(), explorer))
+        .collect();
+    assert_eq!(robots.len(), 4);
+    let expected = SteeringSet(vec![
+        (1.0, Steering::Wander),
+        (2.0, Steering::Separation(30.0)),
+    ]);
+    for (set, explorer) in robots {
+        assert_eq!(set, explorer.then(|| expected.clone()));
+    }
+
+    match SimulationConfig::parse("(steering: { \"Explorer\": [(-1.0, Wander)] })").unwrap_err() {
+        ConfigError::Invalid { field, .. } => assert_eq!(field, "steering.Explorer"),
+        other => panic!("unexpected error: {other}"),
+    }
+
+    let config = SimulationConfig::parse(
+        "(steering: { \"Collector\": [(1.0, ArriveResource(20.0)), \
+         (2.0, ObstacleAvoidance(look_ahead: 40.0))] })",
+    )
+    .unwrap();
+    assert_eq!(
+        config.steering["Collector"],
+        [
+            (1.0, Steering::ArriveResource(20.0)),
+            (2.0, Steering::ObstacleAvoidance { look_ahead: 40.0 }),
+        ]
+    );
+    match SimulationConfig::parse(
+        "(steering: { \"Collector\": [(1.0, ObstacleAvoidance(look_ahead: 0.0))] })",
+    )
+    .unwrap_err()
+    {
+        ConfigError::Invalid { field, .. } => assert_eq!(field, "steering.Collector"),
+        other => panic!("unexpected error: {other}"),
+    }
+}
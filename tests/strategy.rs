@@ -0,0 +1,302 @@
+mod common;
+
+use bevy::prelude::*;
+use essaim_robots::common::DiscoveredResources;
+use essaim_robots::config::{ConfigError, FlockingConfig, SimulationConfig};
+use essaim_robots::energy::Disabled;
+use essaim_robots::map::Map;
+use essaim_robots::robot::{Collector, Explorer, Robot, Sensor};
+use essaim_robots::state::RobotState;
+use essaim_robots::steering::DEFAULT_STEERING;
+use essaim_robots::strategy::{
+    BehaviorRegistry, Decision, Observation, RobotBehavior, SeekNearest, Strategy, StrategyPlugin,
+};
+use rand::RngCore;
+use std::sync::{Arc, Mutex};
+
+// Stratégie qui immobilise le robot
+struct Still;
+
+impl RobotBehavior for Still {
+    fn decide(&mut self, _: &Observation, _: &mut dyn RngCore) -> Decision {
+        Decision {
+            speed: Some(0.0),
+            ..Default::default()
+        }
+    }
+}
+
+fn robot(direction: f32) -> Robot {
+    Robot {
+        direction,
+        radius: 5.0,
+        speed: 50.0,
+        throttle: 1.0,
+        max_turn_rate: 1.0,
+        max_deviation: 1.0,
+    }
+}
+
+#[test]
+fn seek_nearest_turns_towards_the_sensed_resource() {
+    let robot = robot(0.0);
+    let sensor = Sensor {
+        range: 100,
+        detecting: true,
+        nearest: Some(Vec2::new(0.0, 50.0)),
+    };
+    let known = DiscoveredResources(vec![]);
+    let config = SimulationConfig::default();
+    let mut rng = rand::rngs::mock::StepRng::new(0, 1);
+
+    let mut observe = |state| {
+        SeekNearest.decide(
+            &Observation {
+                robot: &robot,
+                position: Vec2::ZERO,
+                state,
+                sensor: Some(&sensor),
+                known: &known,
+                config: &config.collectors,
+                flocking: &FlockingConfig::default(),
+                map: &Map::new(16, 16, 16),
+                steering: DEFAULT_STEERING,
+                neighbours: &[],
+                delta: 0.1,
+            },
+            &mut rng,
+        )
+    };
+
+    // Rotation limitée à 2 radians par seconde
+    let heading = observe(RobotState::Approaching).heading.unwrap();
+    assert!((heading - 0.2).abs() < 1e-6);
+    assert_eq!(observe(RobotState::Charging), Decision::default());
+}
+
+#[test]
+fn unknown_strategies_are_rejected() {
+    let mut registry = BehaviorRegistry::default();
+    let mut config = SimulationConfig::default();
+    config.collectors.strategy = Some("Still".to_string());
+
+    match registry.check(&config).unwrap_err() {
+        ConfigError::Invalid { field, .. } => assert_eq!(field, "explorers.strategy"),
+        other => panic!("unexpected error: {other}"),
+    }
+
+    registry.register("RandomWalk", || Still);
+    match registry.check(&config).unwrap_err() {
+        ConfigError::Invalid { field, .. } => assert_eq!(field, "collectors.strategy"),
+        other => panic!("unexpected error: {other}"),
+    }
+
+    registry.register("Still", || Still);
+    assert!(registry.check(&config).is_ok());
+}
+
+#[test]
+fn an_unknown_strategy_stops_the_simulation() {
+    let mut config = SimulationConfig::default();
+    config.collectors.strategy = Some("Still".to_string());
+
+    let mut app = common::simulation(config);
+    common::start(&mut app);
+    app.update();
+
+    assert_eq!(app.should_exit(), Some(AppExit::error()));
+}
+
+#[test]
+fn known_strategies_keep_the_simulation_running() {
+    let mut app = common::simulation(SimulationConfig::default());
+    common::start(&mut app);
+    app.update();
+
+    assert_eq!(app.should_exit(), None);
+}
+
+#[test]
+fn registered_strategies_are_assigned_from_the_configuration() {
+    // Sans collisions, pour que les collecteurs ne bousculent pas les explorateurs immobiles
+    let mut config = SimulationConfig::default();
+    config.explorers.strategy = Some("Still".to_string());
+    config.collisions.robots = false;
+
+    let mut app = common::simulation(config);
+    app.world_mut()
+        .resource_mut::<BehaviorRegistry>()
+        .register("Still", || Still);
+    common::start(&mut app);
+
+    app.update();
+    app.update();
+    let world = app.world_mut();
+    let start: Vec<Vec2> = world
+        .query_filtered::<&Transform, With<Explorer>>()
+        .iter(world)
+        .map(|transform| transform.translation.truncate())
+        .collect();
+
+    for _ in 0..300 {
+        app.update();
+    }
+
+    let world = app.world_mut();
+    let explorers: Vec<(String, Vec2)> = world
+        .query_filtered::<(&Strategy, &Transform), With<Explorer>>()
+        .iter(world)
+        .map(|(strategy, transform)| (strategy.name.clone(), transform.translation.truncate()))
+        .collect();
+    assert_eq!(explorers.len(), 2);
+    for ((name, position), start) in explorers.iter().zip(&start) {
+        assert_eq!(name, "Still");
+        assert_eq!(position, start);
+    }
+
+    let collectors: Vec<String> = world
+        .query_filtered::<&Strategy, Without<Explorer>>()
+        .iter(world)
+        .map(|strategy| strategy.name.clone())
+        .collect();
+    assert_eq!(collectors, ["SeekNearest", "SeekNearest"]);
+}
+
+#[test]
+fn robot_plugin_brings_the_built_in_strategies() {
+    let mut app = common::simulation(SimulationConfig::default());
+    assert!(app.is_plugin_added::<StrategyPlugin>());
+    common::start(&mut app);
+
+    app.update();
+    let world = app.world_mut();
+    let start: Vec<Vec2> = world
+        .query_filtered::<&Transform, With<Explorer>>()
+        .iter(world)
+        .map(|transform| transform.translation.truncate())
+        .collect();
+
+    for _ in 0..300 {
+        app.update();
+    }
+
+    let world = app.world_mut();
+    let explorers: Vec<(String, Vec2)> = world
+        .query_filtered::<(&Strategy, &Transform), With<Explorer>>()
+        .iter(world)
+        .map(|(strategy, transform)| (strategy.name.clone(), transform.translation.truncate()))
+        .collect();
+    for ((name, position), start) in explorers.iter().zip(&start) {
+        assert_eq!(name, "RandomWalk");
+        assert!(position.distance(*start) > 0.0);
+    }
+}
+
+// Ce qu'a observé un robot guidé par `Recorder`
+struct Seen {
+    collector: bool,
+    range: f32,
+    neighbours: Vec<Entity>,
+}
+
+// Stratégie qui note ses observations, en demandant ou non ses voisins
+struct Recorder {
+    needs_neighbours: bool,
+    seen: Arc<Mutex<Vec<Seen>>>,
+}
+
+impl RobotBehavior for Recorder {
+    fn decide(&mut self, observation: &Observation, _: &mut dyn RngCore) -> Decision {
+        self.seen.lock().unwrap().push(Seen {
+            collector: observation.config.capacity > 0,
+            range: observation.flocking.range,
+            neighbours: observation
+                .neighbours
+                .iter()
+                .map(|neighbour| neighbour.entity)
+                .collect(),
+        });
+        Decision::default()
+    }
+
+    fn needs_neighbours(&self) -> bool {
+        self.needs_neighbours
+    }
+}
+
+// Simulation où les explorateurs observent leurs voisins et les collecteurs non
+fn recorded(config: SimulationConfig) -> (App, Arc<Mutex<Vec<Seen>>>) {
+    let mut config = config;
+    config.explorers.strategy = Some("Watching".to_string());
+    config.collectors.strategy = Some("Blind".to_string());
+    let seen = Arc::new(Mutex::new(vec![]));
+
+    let mut app = common::simulation(config);
+    let mut registry = app.world_mut().resource_mut::<BehaviorRegistry>();
+    for (name, needs_neighbours) in [("Watching", true), ("Blind", false)] {
+        let seen = seen.clone();
+        registry.register(name, move || Recorder {
+            needs_neighbours,
+            seen: seen.clone(),
+        });
+    }
+    common::start(&mut app);
+    (app, seen)
+}
+
+#[test]
+fn neighbours_are_gathered_with_the_rules_of_the_role() {
+    let mut config = SimulationConfig::default();
+    config.flocking.insert(
+        "Explorer".to_string(),
+        FlockingConfig {
+            range: 500.0,
+            ..Default::default()
+        },
+    );
+    let (mut app, seen) = recorded(config);
+    // Les premiers pas de temps précèdent l'indexation des robots
+    app.update();
+    app.update();
+    seen.lock().unwrap().clear();
+
+    for _ in 0..5 {
+        app.update();
+    }
+
+    // Tous partent de la base : les explorateurs voient les autres, les collecteurs personne
+    let seen = seen.lock().unwrap();
+    assert!(!seen.is_empty());
+    for seen in seen.iter() {
+        if seen.collector {
+            assert_eq!(seen.range, FlockingConfig::default().range);
+            assert!(seen.neighbours.is_empty());
+        } else {
+            assert_eq!(seen.range, 500.0);
+            assert_eq!(seen.neighbours.len(), 3);
+        }
+    }
+}
+
+#[test]
+fn disabled_robots_are_not_neighbours() {
+    let (mut app, seen) = recorded(SimulationConfig::default());
+    app.update();
+
+    let world = app.world_mut();
+    let disabled = world
+        .query_filtered::<Entity, With<Collector>>()
+        .iter(world)
+        .next()
+        .unwrap();
+    world.entity_mut(disabled).insert(Disabled);
+    app.update();
+    seen.lock().unwrap().clear();
+
+    for _ in 0..5 {
+        app.update();
+    }
+    let seen = seen.lock().unwrap();
+    assert!(seen.iter().any(|seen| !seen.neighbours.is_empty()));
+    assert!(seen.iter().all(|seen| !seen.neighbours.contains(&disabled)));
+}